@@ -0,0 +1,43 @@
+#!/usr/bin/env bash
+
+export RUST_BACKTRACE=1
+
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+
+export NETWORK_NAME="f8k-ethereum"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+export MINER_COUNT=3
+export CONSENSUS_ENGINE="aura"
+
+# 5 ether until block 1000000, 3 ether until block 2000000, then 2 ether
+export AURA_CONSENSUS_PARAMETERS='{
+  "blockPeriod": 5,
+  "blockReward": {
+    "0": "0x4563918244f40000",
+    "1000000": "3000000000000000000",
+    "0x1e8480": "0x1bc16d674ec80000"
+  }
+}'
+cargo run generate-chainspec
+
+# block reward contract takes over from block 3000000
+export AURA_CONSENSUS_PARAMETERS='{
+  "blockPeriod": 5,
+  "blockReward": { "0": "0x4563918244f40000" },
+  "blockRewardContractAddress": "0x0000000000000000000000000000000000002000",
+  "blockRewardContractTransition": 3000000,
+  "blockRewardContractCode": "0x6080604052600080fd00"
+}'
+cargo run generate-chainspec
+
+# reward contract without code in genesis is rejected
+AURA_CONSENSUS_PARAMETERS='{ "blockRewardContractAddress": "0x0000000000000000000000000000000000002000" }' cargo run generate-chainspec
+
+# empty schedule is rejected
+AURA_CONSENSUS_PARAMETERS='{ "blockReward": {} }' cargo run generate-chainspec
+
+# tendermint takes a constant reward, written as hex quantity
+CONSENSUS_ENGINE="tendermint" TENDERMINT_CONSENSUS_PARAMETERS='{ "blockReward": "3000000000000000000" }' cargo run generate-chainspec
+
+# schedule of tendermint is rejected
+CONSENSUS_ENGINE="tendermint" TENDERMINT_CONSENSUS_PARAMETERS='{ "blockReward": { "0": "0x4563918244f40000", "1000000": "0x29a2241af62c0000" } }' cargo run generate-chainspec
@@ -0,0 +1,26 @@
+#!/usr/bin/env bash
+
+export RUST_BACKTRACE=1
+
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+
+export NETWORK_NAME="f8k-ethereum"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+export MINER_COUNT=3
+export CONSENSUS_ENGINE="aura"
+
+export AURA_CONSENSUS_PARAMETERS='{
+  "blockPeriod": 5,
+  "maximumUncleCount": 0,
+  "emptyStepsTransition": 1000,
+  "maximumEmptySteps": 3,
+  "blockRewardContractAddress": "0x0000000000000000000000000000000000002000",
+  "blockRewardContractCode": "0x6080604052600080fd00"
+}'
+cargo run generate-chainspec
+
+# misspelled key is rejected with the name of variable and key
+AURA_CONSENSUS_PARAMETERS='{ "blockperiod": 5 }' cargo run generate-chainspec
+
+# block period must be greater than 0
+AURA_CONSENSUS_PARAMETERS='{ "blockPeriod": 0 }' cargo run generate-chainspec
@@ -0,0 +1,99 @@
+#!/usr/bin/env bash
+
+export RUST_BACKTRACE=1
+
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+
+export NETWORK_NAME="f8k-ethereum"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+export MINER_COUNT=3
+export CONSENSUS_ENGINE="aura"
+
+# artifacts are looked up by contract name in all subdirectories
+export ACCOUNT_ARTIFACTS_DIR=$(mktemp -d)
+mkdir -p $ACCOUNT_ARTIFACTS_DIR/contracts/Token.sol
+cat >$ACCOUNT_ARTIFACTS_DIR/contracts/Token.sol/Token.json <<EOT
+{
+  "contractName": "Token",
+  "abi": [
+    {
+      "type": "constructor",
+      "inputs": [{ "name": "name", "type": "string" }, { "name": "supply", "type": "uint256" }]
+    }
+  ],
+  "bytecode": "0x6080604052348015600f57600080fd5b50",
+  "deployedBytecode": "0x6080604052600080fd00",
+  "storageLayout": {
+    "storage": [
+      { "label": "totalSupply", "offset": 0, "slot": "0", "type": "t_uint256" },
+      { "label": "owner", "offset": 0, "slot": "1", "type": "t_address" },
+      { "label": "paused", "offset": 20, "slot": "1", "type": "t_bool" },
+      { "label": "name", "offset": 0, "slot": "2", "type": "t_string_storage" },
+      { "label": "balances", "offset": 0, "slot": "3", "type": "t_mapping(t_address,t_uint256)" },
+      {
+        "label": "allowed",
+        "offset": 0,
+        "slot": "4",
+        "type": "t_mapping(t_address,t_mapping(t_address,t_uint256))"
+      },
+      { "label": "holders", "offset": 0, "slot": "5", "type": "t_array(t_address)dyn_storage" }
+    ],
+    "types": {
+      "t_address": { "encoding": "inplace", "label": "address", "numberOfBytes": "20" },
+      "t_bool": { "encoding": "inplace", "label": "bool", "numberOfBytes": "1" },
+      "t_uint256": { "encoding": "inplace", "label": "uint256", "numberOfBytes": "32" },
+      "t_string_storage": { "encoding": "bytes", "label": "string", "numberOfBytes": "32" },
+      "t_mapping(t_address,t_uint256)": {
+        "encoding": "mapping",
+        "key": "t_address",
+        "label": "mapping(address => uint256)",
+        "numberOfBytes": "32",
+        "value": "t_uint256"
+      },
+      "t_mapping(t_address,t_mapping(t_address,t_uint256))": {
+        "encoding": "mapping",
+        "key": "t_address",
+        "label": "mapping(address => mapping(address => uint256))",
+        "numberOfBytes": "32",
+        "value": "t_mapping(t_address,t_uint256)"
+      },
+      "t_array(t_address)dyn_storage": {
+        "base": "t_address",
+        "encoding": "dynamic_array",
+        "label": "address[]",
+        "numberOfBytes": "32"
+      }
+    }
+  }
+}
+EOT
+
+export GENESIS_CONTRACTS_FILE=$(mktemp --suffix=.json)
+cat >$GENESIS_CONTRACTS_FILE <<EOT
+{
+  "0x0000000000000000000000000000000000002000": {
+    "artifact": "Token",
+    "storage": {
+      "totalSupply": "1000000",
+      "owner": "0x0053f97dc01ce07602b208f844b35e8484acf69f",
+      "paused": true,
+      "name": "F8K Token",
+      "balances[0x0053f97dc01ce07602b208f844b35e8484acf69f]": "1000000",
+      "allowed[0x0053f97dc01ce07602b208f844b35e8484acf69f][0x00000000000000000000000000000000000000aa]": "500",
+      "holders": ["0x0053f97dc01ce07602b208f844b35e8484acf69f"]
+    }
+  },
+  "0x0000000000000000000000000000000000002001": {
+    "artifact": "contracts/Token.sol/Token.json",
+    "constructorArguments": ["F8K Token", "1000000"]
+  }
+}
+EOT
+
+cargo run generate-chainspec
+
+# only Parity Ethereum runs constructors of genesis accounts
+if ETHEREUM_PROGRAM="geth" CONSENSUS_ENGINE="clique" cargo run generate-chainspec; then
+    echo "constructor arguments of genesis contracts must be rejected for Go-Ethereum"
+    exit 1
+fi
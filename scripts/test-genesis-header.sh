@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+
+export RUST_BACKTRACE=1
+
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+
+export NETWORK_NAME="f8k-ethereum"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+export MINER_COUNT=3
+
+# wallets sign with chain ID 8888 while nodes find each other with network ID 0xab23
+export NETWORK_ID="0xab23"
+export CHAIN_ID=8888
+export GENESIS_TIMESTAMP=1600000000
+export GENESIS_EXTRA_DATA="0x66386b2d657468657265756d"
+export GENESIS_AUTHOR="0x0053f97dc01ce07602b208f844b35e8484acf69f"
+
+CONSENSUS_ENGINE="aura" cargo run generate-chainspec
+
+# extraData of Clique genesis block starts with the vanity given above
+CONSENSUS_ENGINE="clique" cargo run generate-chainspec
+
+# extraData longer than maximumExtraDataSize is rejected
+CONSENSUS_ENGINE="aura" GENESIS_EXTRA_DATA="0x$(printf '00%.0s' {1..33})" cargo run generate-chainspec
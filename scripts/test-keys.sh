@@ -0,0 +1,33 @@
+#!/usr/bin/env bash
+
+# WARN: make sure you execute this script in root directory of project
+cargo build
+
+export RUST_BACKTRACE=1
+
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+export MINER_COUNT=3
+
+./target/debug/etherinit keys list-validators
+./target/debug/etherinit keys list-validators --miner-count 5 --hd-path "m/44'/60'/{index}'/0/0"
+./target/debug/etherinit keys show-miner --index 1
+
+KEYSTORE_DIR=$(mktemp -d)
+PASSPHRASE_FILE=$(mktemp)
+echo "sealer-password" >$PASSPHRASE_FILE
+./target/debug/etherinit keys export-keystores --indices 0,2 --keystore-dir $KEYSTORE_DIR \
+  --passphrase-file $PASSPHRASE_FILE --kdf-iterations 1024
+
+# keystore of miner 2 matches index 2 but not index 0, which exits with 1
+./target/debug/etherinit keys verify-keystore --index 2 --passphrase-file $PASSPHRASE_FILE \
+  --keystore-file $KEYSTORE_DIR/miner-02/signer_keyfile.json
+./target/debug/etherinit keys verify-keystore --index 0 --passphrase-file $PASSPHRASE_FILE \
+  --keystore-file $KEYSTORE_DIR/miner-02/signer_keyfile.json
+echo "exit code: $?"
+
+NODE_KEY_SEED="f8k-ethereum-node-keys" ./target/debug/etherinit keys node-id \
+  --node-name ethereum-miner-0 --node-name ethereum-miner-1
+
+NODE_KEY_FILE=$(mktemp)
+echo "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318" >$NODE_KEY_FILE
+./target/debug/etherinit keys node-id --node-key-file $NODE_KEY_FILE
@@ -0,0 +1,42 @@
+#!/usr/bin/env bash
+
+export RUST_BACKTRACE=1
+
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+
+NETWORK_CONFIG_FILE=$(mktemp --suffix=.toml)
+cat >$NETWORK_CONFIG_FILE <<EOF
+name = "f8k-ethereum"
+networkId = "0xab23"
+genesisBlockGasLimit = "0x6422c84"
+minerCount = 3
+sealerIntrinsicBalance = "777864512312937"
+
+[consensus]
+engine = "aura"
+
+[consensus.parameters]
+blockPeriod = 5
+blockReward = "6000000000000000000"
+
+[forks]
+petersburg = 0
+istanbul = 1000
+
+[forks.eips]
+eip2028 = 500
+
+[accountStates."0x0053f97dc01ce07602b208f844b35e8484acf69f"]
+balance = "8908974907345139"
+nonce = "29"
+EOF
+
+# environment variables override settings of network config file
+MINER_COUNT=4 cargo run generate-chainspec --network-config-file $NETWORK_CONFIG_FILE
+
+# Go-Ethereum only schedules whole hard forks, activation blocks of single EIPs are rejected
+sed -i 's/engine = "aura"/engine = "clique"/' $NETWORK_CONFIG_FILE
+if MINER_COUNT=4 cargo run generate-chainspec --network-config-file $NETWORK_CONFIG_FILE; then
+    echo "fork schedule with EIP overrides must be rejected for Go-Ethereum"
+    exit 1
+fi
@@ -0,0 +1,45 @@
+#!/usr/bin/env bash
+
+# WARN: make sure you execute this script in root directory of project
+cargo build
+
+export RUST_BACKTRACE=1
+
+ROOT_PREFIX="/tmp/ethereum-launcher-test"
+
+export NETWORK_NAME="f8k-ethereum"
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+export MINER_COUNT=2
+export CONSENSUS_ENGINE="aura"
+export ETHEREUM_PROGRAM="parity"
+
+export NODE_KEY_SEED="f8k-ethereum-node-keys"
+
+# enode URLs of pods behind headless services, the node name is the first label of host
+export NODE_HOSTS="ethereum-miner-0.ethereum-miner:30303,ethereum-miner-1.ethereum-miner,transactor@10.0.0.7:30304"
+ENODES_FILE=$(mktemp)
+./target/debug/etherinit generate-chainspec --enodes-file $ENODES_FILE >/dev/null
+cat $ENODES_FILE
+
+# miner 0 writes the node key whose node ID is the first in the list
+export BOOTNODE_SERVICE_HOST="localhost"
+export BOOTNODE_SERVICE_PORT=9292
+export P2P_NETWORK_SERVICE_PORT=30303
+export HTTP_JSON_RPC_PORT=8545
+export WEBSOCKET_JSON_RPC_PORT=18546
+
+export NODE_ROLE=Miner
+export MINER_INDEX=0
+export NODE_NAME="ethereum-miner-0"
+export CONFIG_ROOT="$ROOT_PREFIX/miner-0"
+export CHAIN_DATA_ROOT="$CONFIG_ROOT/chain-data"
+export BASE_PATH="$CONFIG_ROOT/base"
+RUST_LOG=info ./target/debug/etherinit run-ethereum init
+cat $BASE_PATH/network/key
+
+# Besu miners take their sealer keys as node keys, so enode URLs can not be derived up front
+if ETHEREUM_PROGRAM="besu" CONSENSUS_ENGINE="clique" ./target/debug/etherinit generate-chainspec --enodes-file $ENODES_FILE >/dev/null; then
+    echo "enode URLs of NODE_KEY_SEED must be rejected for Besu"
+    exit 1
+fi
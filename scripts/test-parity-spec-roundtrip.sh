@@ -0,0 +1,19 @@
+#!/usr/bin/env bash
+
+export RUST_BACKTRACE=1
+
+export NETWORK_NAME="f8k-ethereum"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+
+export CONSENSUS_ENGINE="aura"
+export AURA_CONSENSUS_PARAMETERS='{"blockPeriod":5,"blockReward":"6000000000000000000"}'
+
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+export SEALER_INTRINSIC_BALANCE="777864512312937"
+export MINER_COUNT=3
+
+SPEC_FILE=$(mktemp --suffix=.json)
+cargo run generate-chainspec >$SPEC_FILE
+
+# chain specification parsed from spec.json must be rendered as the same spec.json
+diff <(jq -S . $SPEC_FILE) <(cargo run generate-chainspec --parity-spec-file $SPEC_FILE | jq -S .) && echo "round trip ok"
@@ -0,0 +1,29 @@
+#!/usr/bin/env bash
+
+export RUST_BACKTRACE=1
+
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+
+export NETWORK_NAME="f8k-ethereum"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+export MINER_COUNT=3
+export CONSENSUS_ENGINE="aura"
+
+# validators derived on m/44'/60'/0'/0/{index} with empty passphrase, same as before
+cargo run generate-chainspec
+
+# validators derived on a hardware wallet path with a passphrase
+export SEALER_HD_PATH="m/44'/60'/{index}'/0/0"
+export SEALER_MNEMONIC_PASSPHRASE="correct horse battery staple"
+cargo run generate-chainspec
+
+# passphrase read from a file yields the same validators
+unset SEALER_MNEMONIC_PASSPHRASE
+export SEALER_MNEMONIC_PASSPHRASE_FILE=$(mktemp)
+echo "correct horse battery staple" >$SEALER_MNEMONIC_PASSPHRASE_FILE
+cargo run generate-chainspec
+rm $SEALER_MNEMONIC_PASSPHRASE_FILE
+unset SEALER_MNEMONIC_PASSPHRASE_FILE
+
+# HD path without {index} is rejected
+SEALER_HD_PATH="m/44'/60'/0'/0/0" cargo run generate-chainspec
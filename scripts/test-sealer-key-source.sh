@@ -0,0 +1,51 @@
+#!/usr/bin/env bash
+
+# WARN: make sure you execute this script in root directory of project
+cargo build
+
+export RUST_BACKTRACE=1
+
+ROOT_PREFIX="/tmp/ethereum-launcher-test"
+
+export NETWORK_NAME="f8k-ethereum"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+export MINER_COUNT=2
+export CONSENSUS_ENGINE="clique"
+export ETHEREUM_PROGRAM="geth"
+
+# sealer keys of this script are derived from the faucet mnemonic on the default HD path
+export FAUCET_MNEMONIC="rose rocket invest real refuse margin festival danger anger border idle brown"
+export TEST_ACCOUNT_COUNT=$MINER_COUNT
+KEYSTORE_DIR=$(mktemp -d)
+PASSWORD_FILE=$(mktemp)
+echo "sealer-password" >$PASSWORD_FILE
+ACCOUNTS=$(./target/debug/etherinit export-test-accounts --keystore-dir $KEYSTORE_DIR --passphrase-file $PASSWORD_FILE)
+unset FAUCET_MNEMONIC TEST_ACCOUNT_COUNT
+
+# bootnode derives the validator list from plain addresses without any private key
+export VALIDATOR_ADDRESSES=$(echo $ACCOUNTS | jq -r '[.[].address] | join(",")')
+./target/debug/etherinit generate-chainspec
+unset VALIDATOR_ADDRESSES
+
+# miner 0 takes its key from a V3 keystore and a password file
+export NODE_ROLE=Miner
+export CONFIG_ROOT="$ROOT_PREFIX/miner-0"
+export CHAIN_DATA_ROOT="$CONFIG_ROOT/chain-data"
+export MINER_INDEX=0
+export SEALER_KEYSTORE_FILE=$(echo $ACCOUNTS | jq -r '.[0].keystore')
+export SEALER_PASSWORD_FILE=$PASSWORD_FILE
+RUST_LOG=info ./target/debug/etherinit run-ethereum init
+unset SEALER_KEYSTORE_FILE SEALER_PASSWORD_FILE
+
+# miner 1 takes its key from a hex file, like a mounted secret
+export CONFIG_ROOT="$ROOT_PREFIX/miner-1"
+export CHAIN_DATA_ROOT="$CONFIG_ROOT/chain-data"
+export MINER_INDEX=1
+export SEALER_KEY_FILE=$(mktemp)
+echo $ACCOUNTS | jq -r '.[1].privateKey' >$SEALER_KEY_FILE
+RUST_LOG=info ./target/debug/etherinit run-ethereum init
+
+# wrong password is rejected before the node is configured
+export SEALER_KEYSTORE_FILE=$(echo $ACCOUNTS | jq -r '.[0].keystore')
+export SEALER_PASSWORD_FILE=$(mktemp)
+RUST_LOG=info ./target/debug/etherinit run-ethereum init
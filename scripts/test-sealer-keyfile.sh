@@ -0,0 +1,45 @@
+#!/usr/bin/env bash
+
+# WARN: make sure you execute this script in root directory of project
+cargo build
+
+export RUST_BACKTRACE=1
+
+ROOT_PREFIX="/tmp/ethereum-launcher-test"
+
+export NETWORK_NAME="f8k-ethereum"
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+export MINER_COUNT=1
+export ETHEREUM_PROGRAM="geth"
+export CONSENSUS_ENGINE="clique"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+
+export BOOTNODE_SERVICE_HOST="localhost"
+export BOOTNODE_SERVICE_PORT=9292
+export P2P_NETWORK_SERVICE_PORT=30303
+export HTTP_JSON_RPC_PORT=8545
+export WEBSOCKET_JSON_RPC_PORT=18546
+
+export NODE_ROLE=Miner
+export MINER_INDEX=0
+export CONFIG_ROOT="$ROOT_PREFIX/miner-0"
+export CHAIN_DATA_ROOT="$CONFIG_ROOT/chain-data"
+
+# random passphrase and keystore id on each initialization, PBKDF2 rounds lowered for tests
+export SEALER_KEYFILE_KDF_ITERATIONS=1024
+for round in 1 2; do
+  RUST_LOG=info ./target/debug/etherinit run-ethereum init
+  stat -c '%a %n' $CONFIG_ROOT/keystore $CONFIG_ROOT/keystore/* $CONFIG_ROOT/sealer_passphrase
+  jq -r '.id, .crypto.kdfparams.c' $CONFIG_ROOT/keystore/signer_keyfile.json
+done
+
+# passphrase supplied from a secret
+export SEALER_KEYFILE_PASSPHRASE_FILE=$(mktemp)
+echo "mounted-secret" >$SEALER_KEYFILE_PASSPHRASE_FILE
+RUST_LOG=info ./target/debug/etherinit run-ethereum init
+cat $CONFIG_ROOT/sealer_passphrase
+
+# keys are removed when the node is re-initialized as a transactor
+export NODE_ROLE=Transactor
+RUST_LOG=info ./target/debug/etherinit run-ethereum init
+ls $CONFIG_ROOT
@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+
+export RUST_BACKTRACE=1
+
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+
+export NETWORK_NAME="f8k-ethereum"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+export MINER_COUNT=3
+export CONSENSUS_ENGINE="aura"
+
+# test accounts are derived from a mnemonic other than that of sealers
+export FAUCET_MNEMONIC="abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+export TEST_ACCOUNT_COUNT=5
+export TEST_ACCOUNT_BALANCE="1000000000000000000000"
+export TEST_ACCOUNT_HD_PATH="m/44'/60'/0'/0/{index}"
+
+cargo run generate-chainspec
+
+PASSPHRASE_FILE=$(mktemp)
+echo "test-accounts" >$PASSPHRASE_FILE
+cargo run export-test-accounts --keystore-dir $(mktemp -d) --passphrase-file $PASSPHRASE_FILE
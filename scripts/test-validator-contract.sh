@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+
+export RUST_BACKTRACE=1
+
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+
+export NETWORK_NAME="f8k-ethereum"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+export MINER_COUNT=3
+
+# validators are stored as `address[]` in storage slot 1 of the validator set contract, slot 0 if
+# storageSlot is absent
+export CONSENSUS_ENGINE="aura"
+export AURA_CONSENSUS_PARAMETERS='{
+  "blockPeriod": 5,
+  "validators": {
+    "safeContract": {
+      "address": "0x0000000000000000000000000000000000001000",
+      "code": "0x6080604052600080fd00",
+      "storageSlot": 1
+    }
+  }
+}'
+
+cargo run generate-chainspec
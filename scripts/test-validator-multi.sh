@@ -0,0 +1,30 @@
+#!/usr/bin/env bash
+
+export RUST_BACKTRACE=1
+
+export SEALER_MNEMONIC_PHRASE="rose rocket invest real refuse margin festival danger anger border idle brown"
+
+export NETWORK_NAME="f8k-ethereum"
+export GENESIS_BLOCK_GAS_LIMIT="0x6422c84"
+export MINER_COUNT=3
+
+# miners 3 and 4 become validators from block 1000
+export CONSENSUS_ENGINE="aura"
+export AURA_CONSENSUS_PARAMETERS='{
+  "blockPeriod": 5,
+  "validators": {
+    "multi": {
+      "0": "list",
+      "1000": { "list": { "minerCount": 5 } },
+      "5000": {
+        "safeContract": {
+          "address": "0x0000000000000000000000000000000000001000",
+          "code": "0x6080604052600080fd00",
+          "minerCount": 5
+        }
+      }
+    }
+  }
+}'
+
+cargo run generate-chainspec
@@ -141,16 +141,21 @@ impl Client {
         })
     }
 
-    pub fn fetch_chainspec(
+    /// revision of chain specification and the chain specification itself
+    pub fn fetch_chainspec_revision(
         &self,
         network_name: &str,
-    ) -> impl Future<Item = JsonValue, Error = Error> {
-        self.get_json(&format!("/ethereum/{}/chainspec", network_name), |data| {
-            match serde_json::from_slice(&data) {
-                Ok(value) => Ok(value),
-                _ => Ok(JsonValue::default()),
-            }
-        })
+    ) -> impl Future<Item = (u64, JsonValue), Error = Error> {
+        self.get_json(
+            &format!("/ethereum/{}/chainspec-revision", network_name),
+            |data| {
+                let mut value: JsonValue = serde_json::from_slice(&data)?;
+                match value["revision"].as_u64() {
+                    Some(revision) => Ok((revision, value["chainspec"].take())),
+                    None => Ok((0, JsonValue::default())),
+                }
+            },
+        )
     }
 
     pub fn fetch_enodes(
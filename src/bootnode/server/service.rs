@@ -59,6 +59,18 @@ impl Service {
         }
     }
 
+    #[get("/ethereum/:network/chainspec-revision")]
+    #[content_type("application/json")]
+    fn ethereum_chainspec_revision(&self, network: String) -> Result<serde_json::Value, ()> {
+        match self.tracker.lock().ethereum().get(&network) {
+            Some(e) => Ok(json!({
+                "revision": e.chainspec_revision(),
+                "chainspec": e.chainspec_json(),
+            })),
+            None => Ok(serde_json::Value::default()),
+        }
+    }
+
     // #[get("/ethereum/:network/nodes")]
     // #[content_type("text/plain")]
     // fn ethereum_nodes_plain(&self, network: String) -> Result<String, ()> {
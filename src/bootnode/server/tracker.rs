@@ -1,18 +1,30 @@
+use ethereum_types::H256;
 use futures::{Async, Future, Poll, Stream};
 use serde_json::Value as JsonValue;
 use std::collections::{HashMap, HashSet};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::timer::Interval;
 use url::Url;
 
-use crate::primitives::{EthereumChainSpec, EthereumNodeUrl, EthereumSystemInfo};
+use crate::primitives::{Error, EthereumChainSpec, EthereumNodeUrl, EthereumSystemInfo, ForkId};
 
 const MINIMUM_NODE_LIFETIME: Duration = Duration::from_secs(60);
 
+/// first revision of chain specification is the start time of bootnode in seconds, so that
+/// revisions served after bootnode restarts stay greater than the ones served before
+fn initial_spec_revision() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(1)
+}
+
 #[derive(Debug, Clone)]
 pub struct EthereumNetwork {
     spec: EthereumChainSpec,
     spec_json: JsonValue,
+    spec_revision: u64,
+    genesis_hash: Option<H256>,
     nodes: HashMap<EthereumNodeUrl, Instant>,
     http_jsonrpc_endpoints: HashSet<Url>,
     ws_jsonrpc_endpoints: HashSet<Url>,
@@ -20,23 +32,62 @@ pub struct EthereumNetwork {
 }
 
 impl EthereumNetwork {
-    pub fn new(spec: EthereumChainSpec, node_lifetime: Duration) -> EthereumNetwork {
-        let spec_json = spec.as_json();
-        EthereumNetwork {
+    pub fn new(spec: EthereumChainSpec, node_lifetime: Duration) -> Result<EthereumNetwork, Error> {
+        let spec_json = spec.as_json()?;
+        let genesis_hash = match spec.genesis_hash() {
+            Ok(hash) => {
+                info!("Genesis block hash of {}: {:?}", spec.name, hash);
+                Some(hash)
+            }
+            Err(err) => {
+                warn!("Failed to compute genesis block hash, error: {}", err);
+                None
+            }
+        };
+
+        Ok(EthereumNetwork {
+            spec_revision: initial_spec_revision(),
             spec,
             spec_json,
+            genesis_hash,
             nodes: Default::default(),
             http_jsonrpc_endpoints: Default::default(),
             ws_jsonrpc_endpoints: Default::default(),
             node_lifetime,
+        })
+    }
+
+    /// serve chain specification as a new revision, returns the revision if it is accepted,
+    /// a revision must not change genesis block of a running network, revisions increase by one
+    pub fn set_chainspec(&mut self, chainspec: EthereumChainSpec) -> Option<u64> {
+        let spec_json = match chainspec.as_json() {
+            Ok(spec_json) => spec_json,
+            Err(err) => {
+                warn!("Reject chain spec of {}, error: {}", chainspec.name, err);
+                return None;
+            }
+        };
+        if spec_json == self.spec_json {
+            return None;
+        }
+
+        if !self.spec.has_same_genesis(&chainspec) {
+            warn!(
+                "Reject chain spec of {} which changes genesis block",
+                chainspec.name
+            );
+            return None;
         }
+
+        self.spec_revision += 1;
+        self.spec_json = spec_json;
+        self.spec = chainspec;
+        Some(self.spec_revision)
     }
 
     #[inline]
-    #[allow(unused)]
-    pub fn set_chainspec(&mut self, chainspec: EthereumChainSpec) {
-        self.spec_json = chainspec.as_json();
-        self.spec = chainspec;
+    pub fn chainspec_revision(&self) -> u64 {
+        self.spec_revision
     }
 
     #[inline]
@@ -94,10 +145,20 @@ impl EthereumNetwork {
     #[inline]
     pub fn system_info(&self) -> EthereumSystemInfo {
         let consensus_engine = self.spec.consensus_engine.clone();
+        let fork_blocks = ForkId::fork_blocks(&self.spec_json);
+        let fork_id = self
+            .genesis_hash
+            .map(|genesis_hash| ForkId::new(&genesis_hash, &fork_blocks, 0));
         EthereumSystemInfo {
+            program: self.spec.program,
             node_count: self.nodes.len(),
             miner_count: consensus_engine.validator_count(),
             consensus_engine,
+            network_id: Some(self.spec.network_id.as_u64()),
+            chainspec_revision: self.spec_revision,
+            genesis_hash: self.genesis_hash,
+            fork_id,
+            fork_blocks,
         }
     }
 
@@ -138,17 +199,16 @@ impl Tracker {
     }
 
     #[allow(dead_code)]
-    pub fn with_chainspecs(specs: &[EthereumChainSpec], node_lifetime: Duration) -> Tracker {
+    pub fn with_chainspecs(
+        specs: &[EthereumChainSpec],
+        node_lifetime: Duration,
+    ) -> Result<Tracker, Error> {
         let mut tracker = Tracker::new();
-        specs
-            .iter()
-            .cloned()
-            .fold(&mut tracker.ethereum, |e, spec| {
-                e.insert(spec.name.clone(), EthereumNetwork::new(spec, node_lifetime));
-                e
-            });
+        for spec in specs.iter().cloned() {
+            tracker.add_ethereum_network(EthereumNetwork::new(spec, node_lifetime)?);
+        }
 
-        tracker
+        Ok(tracker)
     }
 
     pub fn ethereum(&self) -> &HashMap<String, EthereumNetwork> {
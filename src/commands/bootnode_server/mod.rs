@@ -1,16 +1,28 @@
-use futures::{Async, Future};
+use futures::{Async, Future, Stream};
 use parking_lot::Mutex;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::TcpListener;
 use tokio::runtime::Runtime;
+use tokio::timer::Interval;
 use tower_web::ServiceBuilder;
 
 use crate::bootnode::{BootnodeService, BootnodeTracker, EthereumNetwork};
-use crate::primitives::EthereumChainSpec;
+use crate::primitives::{Error as PrimitivesError, EthereumChainSpec};
 use crate::utils::env_var::from_env;
 
-pub fn execute() -> i32 {
+fn load_chainspec(
+    network_config_file: &Option<PathBuf>,
+    parity_spec_file: &Option<PathBuf>,
+) -> Result<EthereumChainSpec, PrimitivesError> {
+    match parity_spec_file {
+        Some(file_path) => EthereumChainSpec::from_parity_spec_file(file_path),
+        None => EthereumChainSpec::from_file_and_env(network_config_file.clone()),
+    }
+}
+
+pub fn execute(network_config_file: Option<PathBuf>, parity_spec_file: Option<PathBuf>) -> i32 {
     env_logger::init();
 
     let mut runtime = match Runtime::new() {
@@ -22,18 +34,28 @@ pub fn execute() -> i32 {
     };
 
     let tracker = {
-        info!("Generating Ethereum chain spec from environment variables...");
-
         // 5 minutes
         let node_lifetime = Duration::from_secs(5 * 60);
-        let spec = match EthereumChainSpec::from_env() {
+        match parity_spec_file {
+            Some(ref file_path) => info!("Loading Ethereum chain spec from {:?}...", file_path),
+            None => info!(
+                "Generating Ethereum chain spec from network config and environment variables..."
+            ),
+        }
+        let spec = match load_chainspec(&network_config_file, &parity_spec_file) {
             Ok(spec) => spec,
             Err(err) => {
                 error!("{}", err);
                 return -1;
             }
         };
-        let network = EthereumNetwork::new(spec, node_lifetime);
+        let network = match EthereumNetwork::new(spec, node_lifetime) {
+            Ok(network) => network,
+            Err(err) => {
+                error!("{}", err);
+                return -1;
+            }
+        };
         let mut tracker = BootnodeTracker::new();
         tracker.add_ethereum_network(network);
 
@@ -72,10 +94,42 @@ pub fn execute() -> i32 {
         .resource(BootnodeService::with_tracker(tracker.clone()))
         .serve(tcp_listener.incoming());
 
+    // chain specification is reloaded periodically, changes are served as new revisions
+    let mut reload_ticker = {
+        let reload_interval = match from_env("CHAINSPEC_RELOAD_INTERVAL")
+            .unwrap_or_else(|_| "60".into())
+            .parse()
+        {
+            Ok(secs) => Duration::from_secs(secs),
+            Err(err) => {
+                error!("failed to parse chain spec reload interval, error: {}", err);
+                return -1;
+            }
+        };
+        Interval::new_interval(reload_interval)
+    };
+
     let poll_fn = futures::future::poll_fn({
         move || {
             let _ = tracker.lock().poll();
 
+            while let Ok(Async::Ready(Some(_))) = reload_ticker.poll() {
+                match load_chainspec(&network_config_file, &parity_spec_file) {
+                    Ok(spec) => {
+                        let network_name = spec.name.clone();
+                        if let Some(revision) = tracker
+                            .lock()
+                            .ethereum_mut()
+                            .get_mut(&network_name)
+                            .and_then(|network| network.set_chainspec(spec))
+                        {
+                            info!("Serve chain spec revision {} of {}", revision, network_name);
+                        }
+                    }
+                    Err(err) => warn!("failed to reload chain spec, error: {}", err),
+                }
+            }
+
             match server.poll() {
                 Ok(Async::Ready(())) => Ok(Async::Ready(())),
                 Ok(Async::NotReady) => Ok(Async::NotReady),
@@ -1,7 +1,44 @@
-use crate::primitives::EthereumChainSpec;
+use std::path::PathBuf;
 
-pub fn generate_chainspec() -> i32 {
-    let spec = match EthereumChainSpec::from_env() {
+use crate::primitives::{Error, EthereumChainSpec, EthereumProgram, NodeKeySeed};
+use crate::utils::env_var::from_env;
+
+/// enode URLs of nodes in NODE_HOSTS, a comma separated list of `[name@]host[:port]`, Besu is
+/// rejected since its miners take their sealer keys as node keys
+fn enode_urls(program: EthereumProgram) -> Result<Vec<String>, Error> {
+    if program == EthereumProgram::Besu {
+        return Err(Error::UnsupportedNodeKeySeed(program));
+    }
+    let seed = match NodeKeySeed::from_env() {
+        Some(seed) => seed,
+        None => return Err(Error::MissingNetworkSetting("NODE_KEY_SEED".to_owned())),
+    };
+
+    from_env("NODE_HOSTS")?
+        .split(',')
+        .map(str::trim)
+        .filter(|node| !node.is_empty())
+        .map(|node| seed.enode_url(node).map(|url| url.to_string()))
+        .collect()
+}
+
+fn write_enodes_file(file_path: &PathBuf, program: EthereumProgram) -> Result<(), Error> {
+    let enode_urls = enode_urls(program)?;
+    std::fs::write(file_path, serde_json::to_string_pretty(&enode_urls)?)?;
+    Ok(())
+}
+
+pub fn generate_chainspec(
+    network_config_file: Option<PathBuf>,
+    parity_spec_file: Option<PathBuf>,
+    enodes_file: Option<PathBuf>,
+) -> i32 {
+    let spec = match parity_spec_file {
+        Some(file_path) => EthereumChainSpec::from_parity_spec_file(&file_path),
+        None => EthereumChainSpec::from_file_and_env(network_config_file),
+    };
+
+    let spec = match spec {
         Ok(spec) => spec,
         Err(err) => {
             eprintln!("{}", err);
@@ -9,10 +46,33 @@ pub fn generate_chainspec() -> i32 {
         }
     };
 
+    let spec_json = match spec.as_json() {
+        Ok(spec_json) => spec_json,
+        Err(err) => {
+            eprintln!("{}", err);
+            return -1;
+        }
+    };
+
     println!(
         "{}",
-        serde_json::to_string_pretty(&spec.as_json())
-            .expect("serde_json::Value is serializable; qed")
+        serde_json::to_string_pretty(&spec_json).expect("serde_json::Value is serializable; qed")
     );
+
+    // printed to stderr, so that the chain specification can be redirected to a file
+    match (spec.genesis_state_root(), spec.genesis_hash()) {
+        (Ok(state_root), Ok(hash)) => {
+            eprintln!("Genesis state root: {:?}", state_root);
+            eprintln!("Genesis block hash: {:?}", hash);
+        }
+        (Err(err), _) | (_, Err(err)) => eprintln!("Genesis block hash is unavailable: {}", err),
+    }
+
+    if let Some(file_path) = enodes_file {
+        if let Err(err) = write_enodes_file(&file_path, spec.program) {
+            eprintln!("{}", err);
+            return -1;
+        }
+    }
     0
 }
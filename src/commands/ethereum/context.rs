@@ -1,10 +1,9 @@
-use hdwallet::mnemonic::{Language, Mnemonic};
 use std::str::FromStr;
 use std::time::Duration;
 
 use crate::ethereum_controller::RestartPolicy;
 use crate::ethereum_launcher::RunningMode;
-use crate::primitives::NodeRole;
+use crate::primitives::{NodeKeySeed, NodeRole, SealerKeySource, DEFAULT_KDF_ITERATIONS};
 use crate::utils::env_var::from_env;
 
 use super::Error;
@@ -50,6 +49,18 @@ pub struct Context {
     /// Parity Ethereum: Maximum number of transactions per sender in the queue.
     pub parity_tx_queue_per_sender: Option<u32>,
 
+    /// file of passphrase of sealer keystore, a random passphrase is used if absent
+    pub sealer_keyfile_passphrase_file: Option<String>,
+
+    /// PBKDF2 rounds of sealer keystore
+    pub sealer_keyfile_kdf_iterations: u32,
+
+    /// seed of devp2p node key, the client generates a random node key if absent
+    pub node_key_seed: Option<NodeKeySeed>,
+
+    /// name of node which its node key is derived from, same as hostname by default
+    pub node_name: Option<String>,
+
     /// hostname of bootnode service
     pub bootnode_service_host: String,
 
@@ -58,6 +69,10 @@ pub struct Context {
 
     /// interval for update enode URL to bootnode service
     pub bootnode_update_interval: Duration,
+
+    /// nodes restart for new chain specification at random within this window, which should be
+    /// shorter than sealing 100 blocks, so that all nodes restart before a changed fork block
+    pub chainspec_restart_window: Duration,
 }
 
 impl Context {
@@ -94,20 +109,17 @@ impl Context {
                             from_env("MINER_INDEX")?.parse()?
                         }
                     };
-                    let seed = from_env("SEALER_MNEMONIC_PHRASE")?;
-                    let mnemonic = match Mnemonic::try_from(Language::English, seed.as_str()) {
-                        Ok(m) => m,
-                        Err(_err) => {
-                            return Err(Error::InvalidMnemonicPhrase(seed));
-                        }
-                    };
+                    let sealer_key = SealerKeySource::from_env()?;
+                    // fail early on unreadable key
+                    sealer_key.secret_key(index)?;
 
                     let parity_gas_floor_target = from_env("PARITY_GAS_FLOOR_TARGET").ok();
                     let parity_gas_cap = from_env("PARITY_GAS_CAP").ok();
 
                     NodeRole::Miner {
-                        sealer_mnemonic: mnemonic,
+                        sealer_key,
                         index,
+                        activation_block: None,
                         parity_gas_cap,
                         parity_gas_floor_target,
                     }
@@ -142,6 +154,11 @@ impl Context {
                     .unwrap_or_else(|_| "5".into())
                     .parse()?,
             ),
+            chainspec_restart_window: Duration::from_secs(
+                from_env("CHAINSPEC_RESTART_WINDOW")
+                    .unwrap_or_else(|_| "120".into())
+                    .parse()?,
+            ),
 
             parity_tx_queue_mem_limit: from_env("PARITY_TX_QUEUE_MEM_LIMIT")
                 .map(|s| s.parse().unwrap_or(4))
@@ -154,6 +171,15 @@ impl Context {
                 .ok(),
 
             parity_logging,
+
+            node_key_seed: NodeKeySeed::from_env(),
+            node_name: from_env("NODE_NAME").or_else(|_| from_env("HOSTNAME")).ok(),
+
+            sealer_keyfile_passphrase_file: from_env("SEALER_KEYFILE_PASSPHRASE_FILE").ok(),
+            sealer_keyfile_kdf_iterations: match from_env("SEALER_KEYFILE_KDF_ITERATIONS") {
+                Ok(iterations) => iterations.parse()?,
+                Err(_) => DEFAULT_KDF_ITERATIONS,
+            },
         })
     }
 
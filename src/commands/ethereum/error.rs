@@ -1,5 +1,6 @@
 use crate::ethereum_controller::Error as EthereumControllerError;
 use crate::network_keeper::Error as NetworkKeeperError;
+use crate::primitives::Error as PrimitivesError;
 use crate::utils::env_var::Error as EnvVarError;
 
 #[derive(Debug, Fail)]
@@ -25,8 +26,8 @@ pub enum Error {
     #[fail(display = "Unknown node role: {}", _0)]
     UnknownNodeRole(String),
 
-    #[fail(display = "Invalid mnemonic phrase {}", _0)]
-    InvalidMnemonicPhrase(String),
+    #[fail(display = "Primitives error: {}", _0)]
+    Primitives(PrimitivesError),
 
     #[fail(display = "Failed to extract miner index from HOSTNAME={}", _0)]
     FailedToExtractMinerIndexFromHostname(String),
@@ -106,6 +107,12 @@ impl From<NetworkKeeperError> for Error {
     }
 }
 
+impl From<PrimitivesError> for Error {
+    fn from(error: PrimitivesError) -> Error {
+        Error::Primitives(error)
+    }
+}
+
 impl From<EthereumControllerError> for Error {
     fn from(error: EthereumControllerError) -> Error {
         Error::EthereumController(error)
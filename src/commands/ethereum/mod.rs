@@ -1,6 +1,8 @@
+use ethereum_types::Address;
 use futures::{Async, Future, IntoFuture, Poll, Stream};
+use serde_json::Value as JsonValue;
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
 use tokio_signal::unix as UnixSignal;
 use tokio_timer::Interval;
@@ -11,20 +13,96 @@ pub use self::error::Error;
 use crate::bootnode::BootnodeClient;
 use crate::ethereum_controller::EthereumController;
 use crate::ethereum_launcher::EthereumLauncher;
-use crate::network_keeper::NetworkKeeper;
-use crate::primitives::{EthereumNodeUrl, EthereumProgram};
-use crate::utils::RetryFuture;
+use crate::network_keeper::{BlockNumberFetcher, NetworkKeeper};
+use crate::primitives::{
+    EthereumChainSpec, EthereumNodeUrl, EthereumProgram, EthereumSystemInfo, ForkId,
+};
+use crate::utils::{secret_file, RetryFuture};
 
 mod context;
 mod error;
 
-type InitialData = (EthereumProgram, serde_json::Value, Vec<EthereumNodeUrl>);
+type InitialData = (
+    EthereumSystemInfo,
+    (u64, serde_json::Value),
+    Vec<EthereumNodeUrl>,
+);
+
+/// nodes restart for new chain specification when the chain head is this many blocks before
+/// the earliest fork block which the new chain specification changes
+const FORK_BLOCK_RESTART_MARGIN: u64 = 100;
+
+/// earliest fork block which `updated` chain specification changes, None if no fork block is
+/// changed, a restart before the changed fork block does not affect the chain
+fn changed_fork_block(current: &JsonValue, updated: &JsonValue) -> Option<u64> {
+    let current_forks = ForkId::fork_blocks(current);
+    let updated_forks = ForkId::fork_blocks(updated);
+
+    current_forks
+        .iter()
+        .filter(|block| !updated_forks.contains(block))
+        .chain(
+            updated_forks
+                .iter()
+                .filter(|block| !current_forks.contains(block)),
+        )
+        .min()
+        .cloned()
+}
+
+/// random delay within `window`, so that nodes of a network do not restart at the same time
+fn restart_jitter(window: Duration) -> Duration {
+    let window_millis = window.as_secs() * 1000 + u64::from(window.subsec_millis());
+    match secret_file::random_bytes(8) {
+        Ok(ref bytes) if window_millis > 0 => {
+            let mut random = [0u8; 8];
+            random.copy_from_slice(bytes);
+            Duration::from_millis(u64::from_be_bytes(random) % window_millis)
+        }
+        _ => Duration::from_secs(0),
+    }
+}
+
+/// new chain specification which waits for the chain head to reach its restart block
+struct PendingChainSpec {
+    revision: u64,
+    chainspec: JsonValue,
+
+    /// earliest fork block which the new chain specification changes
+    fork_block: u64,
+
+    /// chain head when it is fetched the first time, block interval is estimated from it
+    first_head: Option<(Instant, u64)>,
+}
+
+impl PendingChainSpec {
+    fn restart_block(&self) -> u64 {
+        self.fork_block.saturating_sub(FORK_BLOCK_RESTART_MARGIN)
+    }
+
+    /// longest delay of restart at `head` which ends before the fork block, by half of the time
+    /// of remaining blocks, zero if block interval is not known yet
+    fn max_restart_delay(&self, head: u64) -> Duration {
+        match self.first_head {
+            Some((first_seen, first_head)) if head > first_head => {
+                let block_interval = first_seen.elapsed()
+                    / (head - first_head).min(u64::from(u32::max_value())) as u32;
+                block_interval * (self.fork_block.saturating_sub(head) as u32) / 2
+            }
+            _ => Duration::from_secs(0),
+        }
+    }
+}
 
 pub struct EthereumService {
     ethereum_controller: EthereumController,
     network_keeper: NetworkKeeper,
     network_keeper_ticker: Interval,
     shutdown_signal: Box<dyn Future<Item = (), Error = ()> + Send>,
+
+    pending_chainspec: Option<PendingChainSpec>,
+    block_number_fetcher: Option<BlockNumberFetcher>,
+    chainspec_restart_window: Duration,
 }
 
 impl EthereumService {
@@ -32,6 +110,7 @@ impl EthereumService {
         ethereum_controller: EthereumController,
         network_keeper: NetworkKeeper,
         network_keeper_update_interval: Duration,
+        chainspec_restart_window: Duration,
     ) -> EthereumService {
         let network_keeper_ticker = Interval::new_interval(network_keeper_update_interval);
 
@@ -58,6 +137,47 @@ impl EthereumService {
             network_keeper,
             network_keeper_ticker,
             shutdown_signal,
+
+            pending_chainspec: None,
+            block_number_fetcher: None,
+            chainspec_restart_window,
+        }
+    }
+
+    /// restart Ethereum node with new chain specification after `delay`
+    fn schedule_restart(&mut self, chainspec: JsonValue, delay: Duration) {
+        info!("Restarting Ethereum node in {:?}...", delay);
+        self.ethereum_controller.update_chainspec(chainspec, delay);
+    }
+
+    /// check chain head of pending chain specification, Ethereum node restarts when the head
+    /// reaches its restart block
+    fn poll_pending_chainspec(&mut self) {
+        let mut fetcher = match self.block_number_fetcher.take() {
+            Some(fetcher) => fetcher,
+            None => return,
+        };
+
+        match fetcher.poll() {
+            Ok(Async::NotReady) => self.block_number_fetcher = Some(fetcher),
+            Ok(Async::Ready(head)) => match self.pending_chainspec.take() {
+                Some(ref pending) if head >= pending.fork_block => error!(
+                    "Chain head {} has passed fork block {} which chain spec revision {} changes, the revision is refused",
+                    head, pending.fork_block, pending.revision
+                ),
+                Some(pending) if head >= pending.restart_block() => {
+                    // the random delay must not take the restart past the fork block
+                    let delay = restart_jitter(self.chainspec_restart_window)
+                        .min(pending.max_restart_delay(head));
+                    self.schedule_restart(pending.chainspec, delay)
+                }
+                Some(mut pending) => {
+                    pending.first_head.get_or_insert((Instant::now(), head));
+                    self.pending_chainspec = Some(pending);
+                }
+                None => {}
+            },
+            Err(err) => warn!("Failed to fetch block number, error: {}", err),
         }
     }
 }
@@ -83,10 +203,48 @@ impl Future for EthereumService {
             return Err(Error::from(err));
         }
 
+        if let Some((revision, chainspec)) = self.network_keeper.take_chainspec_update() {
+            // a restarted bootnode serves the same chain specification as a greater revision
+            if chainspec == *self.ethereum_controller.chainspec() {
+                info!("Chain spec revision {} is the running one", revision);
+                self.pending_chainspec = None;
+            } else {
+                match changed_fork_block(self.ethereum_controller.chainspec(), &chainspec) {
+                    Some(fork_block) => {
+                        let pending = PendingChainSpec {
+                            revision,
+                            chainspec,
+                            fork_block,
+                            first_head: None,
+                        };
+                        info!(
+                            "Chain spec revision {} fetched, Ethereum node restarts from block {} before fork block {}",
+                            revision, pending.restart_block(), fork_block
+                        );
+                        self.pending_chainspec = Some(pending);
+                        self.block_number_fetcher = Some(self.network_keeper.block_number());
+                    }
+                    None => {
+                        info!("Chain spec revision {} fetched", revision);
+                        self.pending_chainspec = None;
+                        let delay = restart_jitter(self.chainspec_restart_window);
+                        self.schedule_restart(chainspec, delay);
+                    }
+                }
+            }
+        }
+
+        self.poll_pending_chainspec();
+
         match self.network_keeper_ticker.poll() {
             Ok(Async::Ready(_)) => {
                 self.network_keeper.register_enode();
                 self.network_keeper.import_peers();
+                self.network_keeper.check_chainspec();
+                if self.pending_chainspec.is_some() && self.block_number_fetcher.is_none() {
+                    self.block_number_fetcher = Some(self.network_keeper.block_number());
+                    self.poll_pending_chainspec();
+                }
                 Ok(Async::NotReady)
             }
             Ok(Async::NotReady) => Ok(Async::NotReady),
@@ -117,7 +275,7 @@ pub fn fetch_initial_data(
                         .then(|data| match data {
                             Ok(info) => {
                                 info!("System info: {:?}", info);
-                                Ok(info.consensus_engine.program())
+                                Ok(info)
                             }
                             Err(_err) => Err(Error::FailedToFetchSystemInfo),
                         }),
@@ -136,11 +294,8 @@ pub fn fetch_initial_data(
             move || {
                 Box::new(
                     fetcher
-                        .fetch_chainspec(&network_name)
-                        .then(|data| match data {
-                            Ok(spec) => Ok(spec),
-                            Err(_err) => Err(Error::FailedToFetchChainSpec),
-                        }),
+                        .fetch_chainspec_revision(&network_name)
+                        .map_err(|_err| Error::FailedToFetchChainSpec),
                 )
             }
         }),
@@ -186,6 +341,7 @@ struct Payload {
     ethereum_controller: EthereumController,
     ethereum_program: EthereumProgram,
     ethereum_node_endpoint: PathBuf,
+    chainspec_revision: u64,
 }
 
 impl Payload {
@@ -198,7 +354,7 @@ impl Payload {
             }
         };
 
-        let context = match Context::from_system() {
+        let mut context = match Context::from_system() {
             Ok(context) => {
                 info!("Context: {:?}", context);
                 context
@@ -209,7 +365,7 @@ impl Payload {
             }
         };
 
-        let (ethereum_program, chainspec, static_nodes) = {
+        let (system_info, (chainspec_revision, chainspec), static_nodes) = {
             let client = BootnodeClient::new(
                 context.bootnode_service_host.clone(),
                 context.bootnode_service_port,
@@ -224,10 +380,60 @@ impl Payload {
             }
         };
 
+        let ethereum_program = system_info.program;
+
+        // genesis block is verified before launching, only spec.json of Parity is parsed back
+        if let Some(genesis_hash) = system_info.genesis_hash {
+            match ethereum_program {
+                EthereumProgram::Parity | EthereumProgram::Nethermind => {
+                    match EthereumChainSpec::from_parity_spec(&chainspec)
+                        .and_then(|spec| spec.genesis_hash())
+                    {
+                        Ok(hash) if hash == genesis_hash => {
+                            info!("Genesis block hash: {:?}", hash)
+                        }
+                        Ok(hash) => {
+                            error!(
+                                "Genesis block hash {:?} of chain spec mismatches {:?} of bootnode",
+                                hash, genesis_hash
+                            );
+                            return None;
+                        }
+                        Err(err) => warn!("Failed to verify genesis block hash, error: {}", err),
+                    }
+                }
+                EthereumProgram::GoEthereum | EthereumProgram::Besu => {}
+            }
+        }
+
+        // clients only seal with validators of the current block, so a miner which becomes a
+        // validator at a later transition is launched as usual and waits for its activation block
+        if let Some(sealer_key) = context.node_role.validator_keypair() {
+            let sealer_address = Address::from(*sealer_key.public().address());
+            match system_info
+                .consensus_engine
+                .validator_activation_block(&sealer_address)
+            {
+                Some(block) => {
+                    info!(
+                        "Sealer {:?} is a validator from block {}",
+                        sealer_address, block
+                    );
+                    context.node_role.set_activation_block(block);
+                }
+                None => warn!(
+                    "Sealer {:?} is not a validator of {}",
+                    sealer_address, context.network_name
+                ),
+            }
+        }
+
         let launcher = EthereumLauncher {
             program: ethereum_program,
             chainspec,
 
+            network_id: system_info.network_id,
+
             running_mode: context.running_mode,
 
             node_role: context.node_role.clone(),
@@ -245,6 +451,12 @@ impl Payload {
             parity_tx_queue_per_sender: context.parity_tx_queue_per_sender,
 
             parity_logging: context.parity_logging.clone(),
+
+            sealer_keyfile_passphrase_file: context.sealer_keyfile_passphrase_file.clone(),
+            sealer_keyfile_kdf_iterations: context.sealer_keyfile_kdf_iterations,
+
+            node_key_seed: context.node_key_seed.clone(),
+            node_name: context.node_name.clone(),
         };
 
         match launcher.initialize() {
@@ -263,6 +475,7 @@ impl Payload {
             ethereum_controller: EthereumController::new(launcher, restart_policy),
             ethereum_program,
             ethereum_node_endpoint: ipc_path,
+            chainspec_revision,
         })
     }
 }
@@ -290,12 +503,13 @@ pub fn run_full() -> i32 {
         ethereum_controller,
         ethereum_program,
         ethereum_node_endpoint,
+        chainspec_revision,
     } = match Payload::new() {
         Some(payload) => payload,
         None => return -1,
     };
 
-    let network_keeper = NetworkKeeper::new(
+    let mut network_keeper = NetworkKeeper::new(
         context.network_name,
         ethereum_program,
         context.bootnode_service_host,
@@ -304,11 +518,13 @@ pub fn run_full() -> i32 {
         Some(context.http_jsonrpc_port),
         Some(context.websocket_jsonrpc_port),
     );
+    network_keeper.watch_chainspec(chainspec_revision);
 
     let ethereum_service = EthereumService::new(
         ethereum_controller,
         network_keeper,
         context.bootnode_update_interval,
+        context.chainspec_restart_window,
     );
 
     runtime
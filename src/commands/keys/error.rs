@@ -0,0 +1,63 @@
+use crate::ethereum_launcher::Error as LauncherError;
+use crate::primitives::Error as PrimitivesError;
+use crate::utils::env_var::Error as EnvVarError;
+
+#[derive(Debug, Fail)]
+pub enum Error {
+    #[fail(display = "Primitives error: {}", _0)]
+    Primitives(PrimitivesError),
+
+    #[fail(display = "Launcher error: {}", _0)]
+    Launcher(LauncherError),
+
+    #[fail(display = "Environment variable error: {}", _0)]
+    EnvVar(EnvVarError),
+
+    #[fail(display = "IO error: {}", _0)]
+    Io(std::io::Error),
+
+    #[fail(display = "JSON error: {}", _0)]
+    Json(serde_json::Error),
+
+    #[fail(display = "Parse integer error: {}", _0)]
+    ParseInt(std::num::ParseIntError),
+
+    #[fail(display = "Invalid node key in {}", _0)]
+    InvalidNodeKey(String),
+}
+
+impl From<PrimitivesError> for Error {
+    fn from(error: PrimitivesError) -> Error {
+        Error::Primitives(error)
+    }
+}
+
+impl From<LauncherError> for Error {
+    fn from(error: LauncherError) -> Error {
+        Error::Launcher(error)
+    }
+}
+
+impl From<EnvVarError> for Error {
+    fn from(error: EnvVarError) -> Error {
+        Error::EnvVar(error)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Error {
+        Error::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Error {
+        Error::Json(error)
+    }
+}
+
+impl From<std::num::ParseIntError> for Error {
+    fn from(error: std::num::ParseIntError) -> Error {
+        Error::ParseInt(error)
+    }
+}
@@ -0,0 +1,273 @@
+use ethereum_types::Address;
+use ethsign::SecretKey;
+use serde_json::Value as JsonValue;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+use crate::ethereum_launcher::parity;
+use crate::primitives::{
+    keypair_from_sealer_mnemonic, node_id, Error as PrimitivesError, NodeKeySeed, SealerKeySource,
+    SealerMnemonic, DEFAULT_KDF_ITERATIONS,
+};
+use crate::utils::{self, env_var::from_env, exit_code::EXIT_FAILURE, secret_file};
+
+mod error;
+
+use self::error::Error;
+
+#[derive(Debug, StructOpt)]
+pub enum KeysCommand {
+    /// List validator addresses derived from SEALER_MNEMONIC_PHRASE
+    #[structopt(name = "list-validators")]
+    ListValidators {
+        /// Number of miners, MINER_COUNT if absent
+        #[structopt(long = "miner-count")]
+        miner_count: Option<usize>,
+
+        /// HD path template with {index}, SEALER_HD_PATH if absent
+        #[structopt(long = "hd-path")]
+        hd_path: Option<String>,
+    },
+
+    /// Show address and public key of sealer key of a miner
+    #[structopt(name = "show-miner")]
+    ShowMiner {
+        #[structopt(long = "index")]
+        index: usize,
+
+        /// HD path template with {index}, SEALER_HD_PATH if absent
+        #[structopt(long = "hd-path")]
+        hd_path: Option<String>,
+    },
+
+    /// Export V3 keystores of sealer keys, one directory per miner like CONFIG_ROOT/keys
+    #[structopt(name = "export-keystores")]
+    ExportKeystores {
+        /// Comma separated miner indices
+        #[structopt(long = "indices", raw(use_delimiter = "true", required = "true"))]
+        indices: Vec<usize>,
+
+        #[structopt(long = "keystore-dir", parse(from_os_str))]
+        keystore_dir: PathBuf,
+
+        /// File of passphrase which encrypts keystores
+        #[structopt(long = "passphrase-file", parse(from_os_str))]
+        passphrase_file: PathBuf,
+
+        /// PBKDF2 rounds of keystores
+        #[structopt(long = "kdf-iterations")]
+        kdf_iterations: Option<u32>,
+
+        /// HD path template with {index}, SEALER_HD_PATH if absent
+        #[structopt(long = "hd-path")]
+        hd_path: Option<String>,
+    },
+
+    /// Derive enode node IDs from a node key file, or from NODE_KEY_SEED and node names
+    #[structopt(name = "node-id")]
+    NodeId {
+        /// Node key file of Parity or Geth, hex encoded
+        #[structopt(
+            long = "node-key-file",
+            parse(from_os_str),
+            conflicts_with = "node_names"
+        )]
+        node_key_file: Option<PathBuf>,
+
+        #[structopt(long = "node-name")]
+        node_names: Vec<String>,
+    },
+
+    /// Verify that a keystore holds the sealer key of a miner, exits with 1 if it does not
+    #[structopt(name = "verify-keystore")]
+    VerifyKeystore {
+        #[structopt(long = "keystore-file", parse(from_os_str))]
+        keystore_file: PathBuf,
+
+        /// File of passphrase which decrypts keystore
+        #[structopt(long = "passphrase-file", parse(from_os_str))]
+        passphrase_file: PathBuf,
+
+        /// Expected miner index
+        #[structopt(long = "index")]
+        index: usize,
+
+        /// HD path template with {index}, SEALER_HD_PATH if absent
+        #[structopt(long = "hd-path")]
+        hd_path: Option<String>,
+    },
+}
+
+/// sealer mnemonic of environment variables with HD path replaced by `hd_path`
+fn sealer_mnemonic(hd_path: Option<String>) -> Result<SealerMnemonic, Error> {
+    let mut sealer_mnemonic = SealerMnemonic::from_env()?;
+    if let Some(hd_path) = hd_path {
+        sealer_mnemonic.hd_path = hd_path;
+    }
+    Ok(sealer_mnemonic)
+}
+
+fn address_of(secret_key: &SecretKey) -> Address {
+    Address::from(*secret_key.public().address())
+}
+
+fn list_validators(
+    miner_count: Option<usize>,
+    hd_path: Option<String>,
+) -> Result<JsonValue, Error> {
+    let miner_count = match miner_count {
+        Some(miner_count) => miner_count,
+        None => from_env("MINER_COUNT")?.parse()?,
+    };
+    let keypairs = keypair_from_sealer_mnemonic(&sealer_mnemonic(hd_path)?, miner_count)?;
+
+    Ok(json!(keypairs
+        .iter()
+        .map(|secret_key| utils::to_0xhex(&address_of(secret_key)))
+        .collect::<Vec<_>>()))
+}
+
+fn show_miner(index: usize, hd_path: Option<String>) -> Result<JsonValue, Error> {
+    let sealer_mnemonic = sealer_mnemonic(hd_path)?;
+    let secret_key = sealer_mnemonic.secret_key(index)?;
+
+    Ok(json!({
+        "index": index,
+        "hdPath": sealer_mnemonic.hd_path.replace("{index}", &index.to_string()),
+        "address": utils::to_0xhex(&address_of(&secret_key)),
+        "publicKey": utils::bytes_to_0xhex(secret_key.public().bytes()),
+    }))
+}
+
+fn export_keystores(
+    indices: Vec<usize>,
+    keystore_dir: PathBuf,
+    passphrase_file: PathBuf,
+    kdf_iterations: Option<u32>,
+    hd_path: Option<String>,
+) -> Result<JsonValue, Error> {
+    let sealer_mnemonic = sealer_mnemonic(hd_path)?;
+    let passphrase = std::fs::read_to_string(passphrase_file)?
+        .trim_end()
+        .to_owned();
+
+    let mut keystores = Vec::with_capacity(indices.len());
+    for index in indices {
+        let secret_key = sealer_mnemonic.secret_key(index)?;
+        let dir_path = keystore_dir.join(format!("miner-{:02}", index));
+        secret_file::create_private_dir(&dir_path)?;
+        let file_path = parity::create_key_file(
+            &dir_path,
+            &secret_key,
+            &passphrase,
+            kdf_iterations.unwrap_or(DEFAULT_KDF_ITERATIONS),
+        )?;
+        keystores.push(json!({
+            "index": index,
+            "address": utils::to_0xhex(&address_of(&secret_key)),
+            "keystore": file_path,
+        }));
+    }
+
+    Ok(json!(keystores))
+}
+
+fn node_ids(node_key_file: Option<PathBuf>, node_names: Vec<String>) -> Result<JsonValue, Error> {
+    if let Some(file_path) = node_key_file {
+        let content = std::fs::read_to_string(&file_path)?;
+        let node_key = utils::hex_to_bytes(content.trim())
+            .and_then(|secret| SecretKey::from_raw(&secret).ok())
+            .ok_or_else(|| Error::InvalidNodeKey(file_path.display().to_string()))?;
+        return Ok(json!([{ "nodeId": node_id(&node_key) }]));
+    }
+
+    let seed = match NodeKeySeed::from_env() {
+        Some(seed) => seed,
+        None => {
+            return Err(PrimitivesError::MissingNetworkSetting("NODE_KEY_SEED".to_owned()).into())
+        }
+    };
+    Ok(json!(node_names
+        .iter()
+        .map(|name| json!({ "nodeName": name, "nodeId": node_id(&seed.node_key(name)) }))
+        .collect::<Vec<_>>()))
+}
+
+fn verify_keystore(
+    keystore_file: PathBuf,
+    passphrase_file: PathBuf,
+    index: usize,
+    hd_path: Option<String>,
+) -> Result<(bool, JsonValue), Error> {
+    let expected = address_of(&sealer_mnemonic(hd_path)?.secret_key(index)?);
+    let keystore = SealerKeySource::Keystore {
+        key_file: keystore_file,
+        password_file: passphrase_file,
+    };
+    let actual = address_of(&keystore.secret_key(index)?);
+
+    Ok((
+        actual == expected,
+        json!({
+            "index": index,
+            "matches": actual == expected,
+            "address": utils::to_0xhex(&actual),
+            "expectedAddress": utils::to_0xhex(&expected),
+        }),
+    ))
+}
+
+pub fn execute(command: KeysCommand) -> i32 {
+    let result = match command {
+        KeysCommand::ListValidators {
+            miner_count,
+            hd_path,
+        } => list_validators(miner_count, hd_path).map(|output| (true, output)),
+        KeysCommand::ShowMiner { index, hd_path } => {
+            show_miner(index, hd_path).map(|output| (true, output))
+        }
+        KeysCommand::ExportKeystores {
+            indices,
+            keystore_dir,
+            passphrase_file,
+            kdf_iterations,
+            hd_path,
+        } => export_keystores(
+            indices,
+            keystore_dir,
+            passphrase_file,
+            kdf_iterations,
+            hd_path,
+        )
+        .map(|output| (true, output)),
+        KeysCommand::NodeId {
+            node_key_file,
+            node_names,
+        } => node_ids(node_key_file, node_names).map(|output| (true, output)),
+        KeysCommand::VerifyKeystore {
+            keystore_file,
+            passphrase_file,
+            index,
+            hd_path,
+        } => verify_keystore(keystore_file, passphrase_file, index, hd_path),
+    };
+
+    match result {
+        Ok((success, output)) => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&output)
+                    .expect("serde_json::Value is serializable; qed")
+            );
+            if success {
+                0
+            } else {
+                EXIT_FAILURE
+            }
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            -1
+        }
+    }
+}
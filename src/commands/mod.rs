@@ -2,12 +2,16 @@ mod bootnode_server;
 mod chainspec;
 mod ethereum;
 mod keeper;
+mod keys;
 mod liveness;
 mod readiness;
 mod shell;
+mod test_accounts;
 
+use std::path::PathBuf;
 use structopt::StructOpt;
 
+use self::keys::KeysCommand;
 use self::shell::Shell;
 
 #[derive(Debug, StructOpt)]
@@ -20,10 +24,61 @@ pub enum Command {
     },
 
     #[structopt(name = "generate-chainspec")]
-    GenerateChainSpec,
+    GenerateChainSpec {
+        /// Network config file in TOML or YAML, environment variables override its settings
+        #[structopt(long = "network-config-file", parse(from_os_str))]
+        network_config_file: Option<PathBuf>,
+
+        /// Existing spec.json of Parity Ethereum, replaces network config and environment variables
+        #[structopt(
+            long = "parity-spec-file",
+            parse(from_os_str),
+            conflicts_with = "network_config_file"
+        )]
+        parity_spec_file: Option<PathBuf>,
+
+        /// File to write enode URLs of NODE_HOSTS into, their node keys are derived from
+        /// NODE_KEY_SEED
+        #[structopt(long = "enodes-file", parse(from_os_str))]
+        enodes_file: Option<PathBuf>,
+    },
+
+    #[structopt(name = "export-test-accounts")]
+    ExportTestAccounts {
+        /// Network config file in TOML or YAML, environment variables override its settings
+        #[structopt(long = "network-config-file", parse(from_os_str))]
+        network_config_file: Option<PathBuf>,
+
+        /// Directory to write V3 keystores of test accounts into
+        #[structopt(long = "keystore-dir", parse(from_os_str))]
+        keystore_dir: Option<PathBuf>,
+
+        /// File of passphrase which encrypts keystores, the passphrase is empty if absent
+        #[structopt(long = "passphrase-file", parse(from_os_str))]
+        passphrase_file: Option<PathBuf>,
+    },
+
+    /// Inspect and export sealer keys and node keys
+    #[structopt(name = "keys")]
+    Keys {
+        #[structopt(subcommand)]
+        command: KeysCommand,
+    },
 
     #[structopt(name = "run-bootnode-server")]
-    RunBootnodeServer,
+    RunBootnodeServer {
+        /// Network config file in TOML or YAML, environment variables override its settings
+        #[structopt(long = "network-config-file", parse(from_os_str))]
+        network_config_file: Option<PathBuf>,
+
+        /// Existing spec.json of Parity Ethereum, replaces network config and environment variables
+        #[structopt(
+            long = "parity-spec-file",
+            parse(from_os_str),
+            conflicts_with = "network_config_file"
+        )]
+        parity_spec_file: Option<PathBuf>,
+    },
 
     #[structopt(name = "run-network-keeper")]
     RunNetworkKeeper,
@@ -66,9 +121,28 @@ impl Command {
                 0
             }
 
-            Command::GenerateChainSpec => chainspec::generate_chainspec(),
-
-            Command::RunBootnodeServer => bootnode_server::execute(),
+            Command::GenerateChainSpec {
+                network_config_file,
+                parity_spec_file,
+                enodes_file,
+            } => chainspec::generate_chainspec(network_config_file, parity_spec_file, enodes_file),
+
+            Command::ExportTestAccounts {
+                network_config_file,
+                keystore_dir,
+                passphrase_file,
+            } => test_accounts::export_test_accounts(
+                network_config_file,
+                keystore_dir,
+                passphrase_file,
+            ),
+
+            Command::Keys { command } => keys::execute(command),
+
+            Command::RunBootnodeServer {
+                network_config_file,
+                parity_spec_file,
+            } => bootnode_server::execute(network_config_file, parity_spec_file),
             Command::RunNetworkKeeper => keeper::execute(),
 
             Command::RunEthereum { runlevel } => match runlevel {
@@ -0,0 +1,43 @@
+use crate::bootnode::BootnodeClientError;
+
+#[derive(Debug, Fail)]
+pub enum Error {
+    #[fail(display = "Bootnode client error: {}", _0)]
+    BootnodeClient(BootnodeClientError),
+
+    #[fail(display = "IO error: {}", _0)]
+    Io(std::io::Error),
+
+    #[fail(display = "JSON error: {}", _0)]
+    Json(serde_json::Error),
+
+    #[fail(display = "Invalid JSON-RPC response: {}", _0)]
+    InvalidResponse(String),
+
+    #[fail(display = "Web3 error: {}", _0)]
+    Web3(String),
+}
+
+impl From<BootnodeClientError> for Error {
+    fn from(error: BootnodeClientError) -> Error {
+        Error::BootnodeClient(error)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Error {
+        Error::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Error {
+        Error::Json(error)
+    }
+}
+
+impl From<web3::Error> for Error {
+    fn from(error: web3::Error) -> Error {
+        Error::Web3(error.to_string())
+    }
+}
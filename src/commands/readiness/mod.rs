@@ -1,13 +1,79 @@
+use ethereum_types::H256;
 use futures::{future, Async, Future, Poll};
 use log::LevelFilter;
+use serde_json::Value as JsonValue;
+use std::path::PathBuf;
+use std::str::FromStr;
 use tokio::runtime::Runtime;
 use web3::types::SyncState;
+use web3::Transport;
 
+use crate::bootnode::BootnodeClient;
+use crate::ethereum_client::{self, Error as EthereumClientError};
+use crate::primitives::{EthereumProgram, EthereumSystemInfo, ForkId};
 use crate::utils::{
+    self,
     env_var::from_env,
     exit_code::{EXIT_FAILURE, EXIT_SUCCESS},
 };
 
+mod error;
+use self::error::Error;
+
+/// network name and bootnode client, fork id is not checked if bootnode is not configured
+fn bootnode_from_env() -> Option<(String, BootnodeClient)> {
+    let network_name = from_env("NETWORK_NAME").ok()?;
+    let bootnode_host = from_env("BOOTNODE_SERVICE_HOST").ok()?;
+    let bootnode_port = from_env("BOOTNODE_SERVICE_PORT").ok()?.parse().ok()?;
+    Some((
+        network_name,
+        BootnodeClient::new(bootnode_host, bootnode_port),
+    ))
+}
+
+/// genesis hash and head block number of local node
+fn fetch_local_chain<T: Transport>(
+    web3: &web3::Web3<T>,
+) -> impl Future<Item = (H256, u64), Error = Error> {
+    let transport = web3.transport();
+    transport
+        .execute("eth_getBlockByNumber", vec![json!("0x0"), json!(false)])
+        .join(transport.execute("eth_blockNumber", vec![]))
+        .from_err::<Error>()
+        .and_then(|(genesis, head)| -> Result<(H256, u64), Error> {
+            let genesis_hash = genesis["hash"]
+                .as_str()
+                .and_then(|hash| H256::from_str(utils::clean_0x(hash)).ok())
+                .ok_or_else(|| Error::InvalidResponse(genesis.to_string()))?;
+            let head = head
+                .as_str()
+                .and_then(utils::maybe_u256)
+                .ok_or_else(|| Error::InvalidResponse(head.to_string()))?;
+            Ok((genesis_hash, head.low_u64()))
+        })
+}
+
+/// system information of network, None if bootnode is unreachable
+fn fetch_system_info(
+    network_name: &str,
+    bootnode_client: &BootnodeClient,
+) -> impl Future<Item = Option<EthereumSystemInfo>, Error = Error> {
+    bootnode_client
+        .fetch_system_info(network_name)
+        .then(|result| match result {
+            Ok(system_info) => Ok(Some(system_info)),
+            Err(err) => {
+                warn!("Failed to fetch system info from bootnode, error: {}", err);
+                Ok(None)
+            }
+        })
+}
+
+fn local_fork_blocks(chainspec_file_path: &PathBuf) -> Result<Vec<u64>, Error> {
+    let chainspec: JsonValue = serde_json::from_reader(std::fs::File::open(chainspec_file_path)?)?;
+    Ok(ForkId::fork_blocks(&chainspec))
+}
+
 pub fn execute() -> i32 {
     simple_logging::log_to_stderr(LevelFilter::Info);
 
@@ -19,39 +85,106 @@ pub fn execute() -> i32 {
         }
     };
 
+    let ethereum_program = match EthereumProgram::from_str(
+        &from_env("ETHEREUM_PROGRAM").unwrap_or_else(|_| "parity".to_owned()),
+    ) {
+        Ok(program) => program,
+        Err(err) => {
+            error!("{:?}", err);
+            return -1;
+        }
+    };
+
     let mut runtime = Runtime::new().unwrap();
 
-    let mut web3_ipc = web3::transports::Ipc::new(ethereum_node_endpoint).unwrap();
+    let ethereum_client = ethereum_client::from_program(ethereum_program);
+    let chainspec_file_path = {
+        let mut path = PathBuf::from(from_env("CONFIG_ROOT").unwrap_or_else(|_| "/".to_owned()));
+        path.push(ethereum_client.chainspec_file_name());
+        path
+    };
+
+    let web3_ipc = web3::transports::Ipc::new(ethereum_node_endpoint).unwrap();
     let web3 = web3::Web3::new(web3_ipc.clone());
 
-    let mut syncing_future = web3.eth().syncing();
-    let poll_fn = future::poll_fn(move || -> Poll<bool, web3::Error> {
-        loop {
+    let mut syncing_future = ethereum_client.sync_state(&web3);
+    let poll_fn = future::poll_fn({
+        let mut web3_ipc = web3_ipc.clone();
+        move || -> Poll<bool, EthereumClientError> {
             let _ = web3_ipc.poll();
             match syncing_future.poll() {
-                Ok(Async::Ready(SyncState::NotSyncing)) => {
-                    return Ok(Async::Ready(true));
-                }
-                Ok(Async::Ready(SyncState::Syncing(_))) => {
-                    return Ok(Async::Ready(false));
-                }
-                Ok(Async::NotReady) => return Ok(Async::NotReady),
-                Err(err) => return Err(err),
+                Ok(Async::Ready(SyncState::NotSyncing)) => Ok(Async::Ready(true)),
+                Ok(Async::Ready(SyncState::Syncing(_))) => Ok(Async::Ready(false)),
+                Ok(Async::NotReady) => Ok(Async::NotReady),
+                Err(err) => Err(err),
             }
         }
     });
 
     match runtime.block_on(poll_fn) {
-        Ok(true) => {
-            info!("Blockchain is not syncing, Ethereum node is ready!");
-            EXIT_SUCCESS
-        }
+        Ok(true) => info!("Blockchain is not syncing"),
         Ok(false) => {
             warn!("Blockchain is syncing, Ethereum node is not ready!");
-            EXIT_FAILURE
+            return EXIT_FAILURE;
         }
         Err(err) => {
             error!("{:?}", err);
+            return EXIT_FAILURE;
+        }
+    }
+
+    let (network_name, bootnode_client) = match bootnode_from_env() {
+        Some(bootnode) => bootnode,
+        None => {
+            info!("Bootnode is not configured, Ethereum node is ready!");
+            return EXIT_SUCCESS;
+        }
+    };
+
+    let local_fork_blocks = match local_fork_blocks(&chainspec_file_path) {
+        Ok(blocks) => blocks,
+        Err(err) => {
+            error!(
+                "Failed to read chain spec {:?}, error: {}",
+                chainspec_file_path, err
+            );
+            return EXIT_FAILURE;
+        }
+    };
+
+    let mut fork_id_future =
+        fetch_local_chain(&web3).join(fetch_system_info(&network_name, &bootnode_client));
+    let poll_fn = future::poll_fn({
+        let mut web3_ipc = web3_ipc.clone();
+        move || {
+            let _ = web3_ipc.poll();
+            fork_id_future.poll()
+        }
+    });
+
+    match runtime.block_on(poll_fn) {
+        Ok(((genesis_hash, head), Some(system_info))) => {
+            let local_fork_id = ForkId::new(&genesis_hash, &local_fork_blocks, head);
+            match system_info.fork_id_at(head) {
+                Some(fork_id) if fork_id != local_fork_id => {
+                    warn!(
+                        "Fork id mismatch at block {}, local: {}, network: {}, Ethereum node is not ready!",
+                        head, local_fork_id, fork_id
+                    );
+                    EXIT_FAILURE
+                }
+                _ => {
+                    info!("Fork id: {}, Ethereum node is ready!", local_fork_id);
+                    EXIT_SUCCESS
+                }
+            }
+        }
+        Ok((_, None)) => {
+            warn!("Fork id is not checked, Ethereum node is ready!");
+            EXIT_SUCCESS
+        }
+        Err(err) => {
+            error!("{}", err);
             EXIT_FAILURE
         }
     }
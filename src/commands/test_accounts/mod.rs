@@ -0,0 +1,78 @@
+use ethereum_types::Address;
+use std::path::PathBuf;
+
+use crate::primitives::{key_file, Error, NetworkConfig, TestAccounts, DEFAULT_KDF_ITERATIONS};
+use crate::utils::{self, secret_file};
+
+/// keystore id derived from address, so that exported keystores are reproducible
+fn keystore_id(address: &Address) -> String {
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&utils::keccak256(&address[..])[..16]);
+    utils::uuid_from_bytes(&bytes)
+}
+
+fn export(
+    network_config_file: Option<PathBuf>,
+    keystore_dir: Option<PathBuf>,
+    passphrase_file: Option<PathBuf>,
+) -> Result<serde_json::Value, Error> {
+    let config = NetworkConfig::from_file_and_env(network_config_file)?;
+    let test_accounts = match TestAccounts::from_network_config(&config)? {
+        Some(test_accounts) => test_accounts,
+        None => return Err(Error::MissingNetworkSetting("faucetMnemonic".to_owned())),
+    };
+
+    let passphrase = match passphrase_file {
+        Some(file_path) => std::fs::read_to_string(file_path)?.trim_end().to_owned(),
+        None => String::new(),
+    };
+    if let Some(ref dir_path) = keystore_dir {
+        secret_file::create_private_dir(dir_path)?;
+    }
+
+    let mut accounts = Vec::with_capacity(test_accounts.count);
+    for (index, secret_key) in test_accounts.secret_keys()?.iter().enumerate() {
+        let address = Address::from(*secret_key.public().address());
+        let mut account = json!({
+            "index": index,
+            "address": utils::to_0xhex(&address),
+            "privateKey": utils::bytes_to_0xhex(secret_key.secret()),
+        });
+
+        if let Some(ref dir_path) = keystore_dir {
+            let keystore = key_file(
+                secret_key,
+                &passphrase,
+                keystore_id(&address),
+                DEFAULT_KDF_ITERATIONS,
+            )?;
+            let file_path = dir_path.join(format!("{:x}.json", address));
+            secret_file::write_private_file(&file_path, &serde_json::to_vec(&keystore)?)?;
+            account["keystore"] = json!(file_path);
+        }
+        accounts.push(account);
+    }
+
+    Ok(json!(accounts))
+}
+
+pub fn export_test_accounts(
+    network_config_file: Option<PathBuf>,
+    keystore_dir: Option<PathBuf>,
+    passphrase_file: Option<PathBuf>,
+) -> i32 {
+    match export(network_config_file, keystore_dir, passphrase_file) {
+        Ok(accounts) => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&accounts)
+                    .expect("serde_json::Value is serializable; qed")
+            );
+            0
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            -1
+        }
+    }
+}
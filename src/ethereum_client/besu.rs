@@ -0,0 +1,144 @@
+use futures::Future;
+use std::path::PathBuf;
+use std::process::Command;
+use web3::Transport;
+
+use crate::ethereum_launcher::{besu, geth, Error as LauncherError, EthereumLauncher};
+use crate::primitives::{EthereumNodeUrl, KeyPair};
+use crate::utils::secret_file;
+
+use super::{EnodeFetcher, Error, EthereumClient, PeerUpdater, Web3};
+
+const BESU_EXECUTABLE_PATH: &str = "besu";
+
+pub struct Besu;
+
+impl Besu {
+    fn config(
+        &self,
+        launcher: &EthereumLauncher,
+        genesis_file_path: &PathBuf,
+    ) -> Result<besu::BesuConfig, LauncherError> {
+        // validators of Clique and IBFT 2.0 are identified by node key in Besu, other nodes may
+        // take a derived node key
+        let has_node_key =
+            launcher.node_role.validator_keypair().is_some() || launcher.node_key().is_some();
+        let node_key_file_path = if has_node_key {
+            Some(
+                besu::node_key_file_path(&launcher.config_dir_path())
+                    .to_str()
+                    .expect("node key file path")
+                    .to_owned(),
+            )
+        } else {
+            None
+        };
+
+        Ok(besu::BesuConfig {
+            running_mode: launcher.running_mode,
+
+            network_id: launcher.network_id()?,
+            data_path: launcher
+                .chain_data_dir_path()
+                .to_str()
+                .expect("data directory path")
+                .to_owned(),
+            genesis_file_path: genesis_file_path
+                .to_str()
+                .expect("genesis file path")
+                .to_owned(),
+            node_key_file_path,
+
+            identity: launcher.node_role.identity(),
+            bootnodes: launcher.bootnodes.clone(),
+
+            ipc_path: launcher.ipc_path().to_str().expect("ipc path").to_owned(),
+            network_port: launcher.network_port,
+            http_jsonrpc_port: launcher.http_jsonrpc_port,
+            websocket_jsonrpc_port: launcher.websocket_jsonrpc_port,
+        })
+    }
+}
+
+impl EthereumClient for Besu {
+    fn ipc_file_name(&self) -> &'static str {
+        "besu.ipc"
+    }
+
+    fn chainspec_file_name(&self) -> &'static str {
+        "genesis.json"
+    }
+
+    fn initialize(&self, launcher: &EthereumLauncher) -> Result<PathBuf, LauncherError> {
+        let genesis_file_path = self.write_chainspec(launcher)?;
+
+        self.config(launcher, &genesis_file_path)?
+            .save_as_file(&launcher.config_file_path())
+    }
+
+    fn write_chainspec(&self, launcher: &EthereumLauncher) -> Result<PathBuf, LauncherError> {
+        geth::create_genesis_file(&launcher.config_dir_path(), &launcher.chainspec)
+    }
+
+    /// Besu does not unlock accounts, sealer key is used as node key instead
+    fn import_key(
+        &self,
+        launcher: &EthereumLauncher,
+        sealer_key: &KeyPair,
+        _passphrase: &str,
+    ) -> Result<PathBuf, LauncherError> {
+        besu::create_node_key_file(&launcher.config_dir_path(), sealer_key.secret())
+    }
+
+    /// miners keep their sealer key as node key
+    fn write_node_key(
+        &self,
+        launcher: &EthereumLauncher,
+        node_key: &KeyPair,
+    ) -> Result<Option<PathBuf>, LauncherError> {
+        if launcher.node_role.is_miner() {
+            return Ok(None);
+        }
+        besu::create_node_key_file(&launcher.config_dir_path(), node_key.secret()).map(Some)
+    }
+
+    fn remove_keys(&self, launcher: &EthereumLauncher) -> Result<(), LauncherError> {
+        secret_file::remove_if_exists(&besu::node_key_file_path(&launcher.config_dir_path()))?;
+        Ok(())
+    }
+
+    fn execute_command(&self, launcher: &EthereumLauncher) -> (Command, Vec<String>) {
+        let config_file_path = String::from(
+            launcher
+                .config_file_path()
+                .to_str()
+                .expect("config file path"),
+        );
+        (
+            Command::new(BESU_EXECUTABLE_PATH),
+            vec![format!("--config-file={}", config_file_path)],
+        )
+    }
+
+    fn enode(&self, web3: &Web3) -> EnodeFetcher {
+        super::admin_node_enode(web3)
+    }
+
+    fn add_peer(&self, web3: &Web3, enode_url: &EthereumNodeUrl) -> PeerUpdater {
+        Box::new(
+            web3.transport()
+                .execute("admin_addPeer", vec![json!(enode_url.to_string())])
+                .map(|result| super::admin_peer_result(&result))
+                .from_err::<Error>(),
+        )
+    }
+
+    fn remove_peer(&self, web3: &Web3, enode_url: &EthereumNodeUrl) -> PeerUpdater {
+        Box::new(
+            web3.transport()
+                .execute("admin_removePeer", vec![json!(enode_url.to_string())])
+                .map(|result| super::admin_peer_result(&result))
+                .from_err::<Error>(),
+        )
+    }
+}
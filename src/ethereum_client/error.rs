@@ -0,0 +1,26 @@
+use crate::primitives::EthereumNodeUrlError;
+
+#[derive(Debug, Fail)]
+pub enum Error {
+    #[fail(display = "Web3 error: {}", _0)]
+    // FIXME use Web3(web3::Error),
+    Web3(String),
+
+    #[fail(display = "EthereumNodeUrl error: {}", _0)]
+    EthereumNodeUrl(EthereumNodeUrlError),
+
+    #[fail(display = "Invalid Ethereum node info: {}", _0)]
+    InvalidNodeInfo(String),
+}
+
+impl From<web3::Error> for Error {
+    fn from(error: web3::Error) -> Error {
+        Error::Web3(error.to_string())
+    }
+}
+
+impl From<EthereumNodeUrlError> for Error {
+    fn from(error: EthereumNodeUrlError) -> Error {
+        Error::EthereumNodeUrl(error)
+    }
+}
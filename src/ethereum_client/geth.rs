@@ -0,0 +1,376 @@
+use ethereum_types::Address;
+use futures::Future;
+use std::path::PathBuf;
+use std::process::Command;
+use web3::Transport;
+
+use crate::ethereum_launcher::{geth, keystore, parity, Error as LauncherError, EthereumLauncher};
+use crate::primitives::{EthereumNodeUrl, KeyPair};
+use crate::utils::secret_file;
+
+use super::{EnodeFetcher, Error, EthereumClient, PeerUpdater, Web3};
+
+const GETH_EXECUTABLE_PATH: &str = "geth";
+
+pub struct GoEthereum;
+
+impl GoEthereum {
+    fn miner_options(&self, launcher: &EthereumLauncher) -> Option<geth::GethMinerOptions> {
+        launcher
+            .node_role
+            .validator_keypair()
+            .map(|sealer_key| geth::GethMinerOptions {
+                sealer_address: Address::from(*sealer_key.public().address()),
+                sealer_passphrase_file_path: keystore::passphrase_file_path(
+                    &launcher.config_dir_path(),
+                )
+                .to_str()
+                .expect("sealer passphrase file path")
+                .to_owned(),
+            })
+    }
+
+    fn config(&self, launcher: &EthereumLauncher) -> Result<geth::GethConfig, LauncherError> {
+        let config_dir = launcher.config_dir_path();
+
+        Ok(geth::GethConfig {
+            running_mode: launcher.running_mode,
+
+            network_id: launcher.network_id()?,
+            data_dir: launcher
+                .chain_data_dir_path()
+                .to_str()
+                .expect("data directory path")
+                .to_owned(),
+            keystore_dir: keystore::keystore_directory_path(&config_dir)
+                .to_str()
+                .expect("keystore directory path")
+                .to_owned(),
+            node_role: launcher.node_role.clone(),
+
+            identity: launcher.node_role.identity(),
+            bootnodes: launcher.bootnodes.clone(),
+
+            ipc_path: launcher.ipc_path().to_str().expect("ipc path").to_owned(),
+            network_port: launcher.network_port,
+            http_jsonrpc_port: launcher.http_jsonrpc_port,
+            websocket_jsonrpc_port: launcher.websocket_jsonrpc_port,
+        })
+    }
+}
+
+impl GoEthereum {
+    /// `geth init` which writes genesis block and chain config into database
+    fn init_command(&self, launcher: &EthereumLauncher, genesis_file_path: &PathBuf) -> Command {
+        let mut command = Command::new(GETH_EXECUTABLE_PATH);
+        command
+            .arg(format!(
+                "--datadir={}",
+                launcher
+                    .chain_data_dir_path()
+                    .to_str()
+                    .expect("data directory path")
+            ))
+            .arg("init")
+            .arg(genesis_file_path.to_str().expect("genesis file path"));
+        command
+    }
+}
+
+impl EthereumClient for GoEthereum {
+    fn ipc_file_name(&self) -> &'static str {
+        "geth.ipc"
+    }
+
+    fn chainspec_file_name(&self) -> &'static str {
+        "genesis.json"
+    }
+
+    /// geth is initialized before it starts, so that waiting for `geth init` blocks nothing
+    fn initialize(&self, launcher: &EthereumLauncher) -> Result<PathBuf, LauncherError> {
+        let config_file_path = self
+            .config(launcher)?
+            .save_as_file(&launcher.config_file_path())?;
+
+        let genesis_file_path = self.write_chainspec(launcher)?;
+        if self
+            .init_command(launcher, &genesis_file_path)
+            .status()?
+            .success()
+        {
+            Ok(config_file_path)
+        } else {
+            Err(LauncherError::FailedToInitializeGenesis)
+        }
+    }
+
+    fn write_chainspec(&self, launcher: &EthereumLauncher) -> Result<PathBuf, LauncherError> {
+        geth::create_genesis_file(&launcher.config_dir_path(), &launcher.chainspec)
+    }
+
+    /// chain config of an initialized database is updated by `geth init` as well
+    fn init_chainspec_command(
+        &self,
+        launcher: &EthereumLauncher,
+        chainspec_file_path: &PathBuf,
+    ) -> Option<Command> {
+        Some(self.init_command(launcher, chainspec_file_path))
+    }
+
+    fn import_key(
+        &self,
+        launcher: &EthereumLauncher,
+        sealer_key: &KeyPair,
+        passphrase: &str,
+    ) -> Result<PathBuf, LauncherError> {
+        let config_dir = launcher.config_dir_path();
+
+        let keystore_dir = keystore::create_keystore_directory(&config_dir)?;
+        let key_file_path = parity::create_key_file(
+            &keystore_dir,
+            sealer_key,
+            passphrase,
+            launcher.sealer_keyfile_kdf_iterations,
+        )?;
+        keystore::create_passphrase_file(&config_dir, passphrase)?;
+
+        Ok(key_file_path)
+    }
+
+    fn write_node_key(
+        &self,
+        launcher: &EthereumLauncher,
+        node_key: &KeyPair,
+    ) -> Result<Option<PathBuf>, LauncherError> {
+        geth::create_node_key_file(&launcher.chain_data_dir_path(), node_key.secret()).map(Some)
+    }
+
+    fn remove_keys(&self, launcher: &EthereumLauncher) -> Result<(), LauncherError> {
+        let config_dir = launcher.config_dir_path();
+        secret_file::remove_if_exists(&keystore::keystore_directory_path(&config_dir))?;
+        secret_file::remove_if_exists(&keystore::passphrase_file_path(&config_dir))?;
+        Ok(())
+    }
+
+    fn execute_command(&self, launcher: &EthereumLauncher) -> (Command, Vec<String>) {
+        let config_file_path = String::from(
+            launcher
+                .config_file_path()
+                .to_str()
+                .expect("config file path"),
+        );
+        (
+            Command::new(GETH_EXECUTABLE_PATH),
+            geth::command_args(&config_file_path, self.miner_options(launcher).as_ref()),
+        )
+    }
+
+    fn enode(&self, web3: &Web3) -> EnodeFetcher {
+        super::admin_node_enode(web3)
+    }
+
+    /// add peer as both trusted and static peer, trusted peers are always allowed to connect
+    fn add_peer(&self, web3: &Web3, enode_url: &EthereumNodeUrl) -> PeerUpdater {
+        let enode_url = json!(enode_url.to_string());
+        let transport = web3.transport();
+
+        Box::new(
+            transport
+                .execute("admin_addTrustedPeer", vec![enode_url.clone()])
+                .join(transport.execute("admin_addPeer", vec![enode_url]))
+                .map(|(trusted, added)| {
+                    trusted.as_bool().unwrap_or(false) && added.as_bool().unwrap_or(false)
+                })
+                .from_err::<Error>(),
+        )
+    }
+
+    fn remove_peer(&self, web3: &Web3, enode_url: &EthereumNodeUrl) -> PeerUpdater {
+        let enode_url = json!(enode_url.to_string());
+        let transport = web3.transport();
+
+        Box::new(
+            transport
+                .execute("admin_removeTrustedPeer", vec![enode_url.clone()])
+                .join(transport.execute("admin_removePeer", vec![enode_url]))
+                .map(|(untrusted, removed)| {
+                    untrusted.as_bool().unwrap_or(false) && removed.as_bool().unwrap_or(false)
+                })
+                .from_err::<Error>(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future;
+    use serde_json::Value as JsonValue;
+    use std::io::Write;
+    use std::os::unix::net::UnixListener;
+    use std::path::Path;
+    use std::sync::mpsc;
+    use std::thread::{self, JoinHandle};
+    use tokio::runtime::Runtime;
+
+    use super::*;
+
+    const NODE_ID: &str = "6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012\
+                           937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0";
+
+    /// JSON-RPC server on Unix socket which answers admin methods like Go-Ethereum, requests
+    /// are sent back through the returned channel
+    fn serve_fake_geth(
+        ipc_path: &Path,
+        add_peer_result: bool,
+    ) -> (JoinHandle<()>, mpsc::Receiver<JsonValue>) {
+        let _ = std::fs::remove_file(ipc_path);
+        let listener = UnixListener::bind(ipc_path).expect("bind fake IPC endpoint");
+        let (request_sender, request_receiver) = mpsc::channel();
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("accept IPC connection");
+            let mut writer = stream.try_clone().expect("clone IPC stream");
+            let requests = serde_json::Deserializer::from_reader(stream).into_iter::<JsonValue>();
+            for request in requests {
+                let request = match request {
+                    Ok(request) => request,
+                    Err(_) => break,
+                };
+                let result = match request["method"].as_str() {
+                    Some("admin_addTrustedPeer") => json!(true),
+                    Some("admin_addPeer") => json!(add_peer_result),
+                    Some("admin_removeTrustedPeer") | Some("admin_removePeer") => json!(true),
+                    Some("admin_nodeInfo") => json!({
+                        "id": NODE_ID,
+                        "enode": format!("enode://{}@127.0.0.1:30304?discport=0", NODE_ID),
+                    }),
+                    _ => JsonValue::Null,
+                };
+                let response = json!({ "jsonrpc": "2.0", "id": request["id"], "result": result });
+                if request_sender.send(request).is_err() {
+                    break;
+                }
+                writer
+                    .write_all(response.to_string().as_bytes())
+                    .expect("write IPC response");
+            }
+        });
+
+        (handle, request_receiver)
+    }
+
+    fn ipc_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("etherinit-{}-{}.ipc", name, std::process::id()))
+    }
+
+    /// run future built from Web3 of IPC endpoint, the transport is polled along with it
+    fn run<T, F>(ipc_path: &Path, f: F) -> Result<T, Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Web3) -> Box<dyn Future<Item = T, Error = Error> + Send>,
+    {
+        let mut runtime = Runtime::new().expect("tokio runtime");
+        let web3_ipc = web3::transports::Ipc::new(ipc_path).expect("connect fake IPC endpoint");
+        let web3 = web3::Web3::new(web3_ipc.clone());
+
+        let mut fut = f(&web3);
+        let mut web3_ipc = web3_ipc.clone();
+        runtime.block_on(future::poll_fn(move || {
+            let _ = web3_ipc.poll();
+            fut.poll()
+        }))
+    }
+
+    #[test]
+    fn add_peer_adds_trusted_and_static_peer() {
+        let ipc_path = ipc_path("geth-add-peer");
+        let (_server, requests) = serve_fake_geth(&ipc_path, true);
+        let enode_url = EthereumNodeUrl {
+            node_id: NODE_ID.to_owned(),
+            host: "10.0.0.2".to_owned(),
+            port: 30303,
+        };
+
+        let added = run(&ipc_path, |web3| GoEthereum.add_peer(web3, &enode_url));
+        assert_eq!(added.ok(), Some(true));
+
+        let mut methods: Vec<_> = requests
+            .try_iter()
+            .map(|request| {
+                assert_eq!(request["params"], json!([enode_url.to_string()]));
+                request["method"].as_str().unwrap_or_default().to_owned()
+            })
+            .collect();
+        methods.sort();
+        assert_eq!(methods, vec!["admin_addPeer", "admin_addTrustedPeer"]);
+
+        let _ = std::fs::remove_file(&ipc_path);
+    }
+
+    #[test]
+    fn remove_peer_removes_trusted_and_static_peer() {
+        let ipc_path = ipc_path("geth-remove-peer");
+        let (_server, requests) = serve_fake_geth(&ipc_path, true);
+        let enode_url = EthereumNodeUrl {
+            node_id: NODE_ID.to_owned(),
+            host: "10.0.0.4".to_owned(),
+            port: 30303,
+        };
+
+        let removed = run(&ipc_path, |web3| GoEthereum.remove_peer(web3, &enode_url));
+        assert_eq!(removed.ok(), Some(true));
+
+        let mut methods: Vec<_> = requests
+            .try_iter()
+            .map(|request| {
+                assert_eq!(request["params"], json!([enode_url.to_string()]));
+                request["method"].as_str().unwrap_or_default().to_owned()
+            })
+            .collect();
+        methods.sort();
+        assert_eq!(methods, vec!["admin_removePeer", "admin_removeTrustedPeer"]);
+
+        let _ = std::fs::remove_file(&ipc_path);
+    }
+
+    #[test]
+    fn enode_is_parsed_from_admin_node_info() {
+        let ipc_path = ipc_path("geth-enode");
+        let (_server, requests) = serve_fake_geth(&ipc_path, true);
+
+        let enode_url = run(&ipc_path, |web3| GoEthereum.enode(web3)).expect("enode URL");
+        assert_eq!(
+            enode_url,
+            EthereumNodeUrl {
+                node_id: NODE_ID.to_owned(),
+                host: "127.0.0.1".to_owned(),
+                port: 30304,
+            }
+        );
+        assert_eq!(
+            requests
+                .try_iter()
+                .next()
+                .map(|request| request["method"].clone()),
+            Some(json!("admin_nodeInfo"))
+        );
+
+        let _ = std::fs::remove_file(&ipc_path);
+    }
+
+    #[test]
+    fn add_peer_fails_if_static_peer_is_rejected() {
+        let ipc_path = ipc_path("geth-add-peer-rejected");
+        let (_server, _requests) = serve_fake_geth(&ipc_path, false);
+        let enode_url = EthereumNodeUrl {
+            node_id: NODE_ID.to_owned(),
+            host: "10.0.0.3".to_owned(),
+            port: 30303,
+        };
+
+        let added = run(&ipc_path, |web3| GoEthereum.add_peer(web3, &enode_url));
+        assert_eq!(added.ok(), Some(false));
+
+        let _ = std::fs::remove_file(&ipc_path);
+    }
+}
@@ -0,0 +1,121 @@
+use futures::Future;
+use std::path::PathBuf;
+use std::process::Command;
+use std::str::FromStr;
+use web3::types::SyncState;
+use web3::Transport;
+
+use crate::ethereum_launcher::{Error as LauncherError, EthereumLauncher};
+use crate::primitives::{EthereumNodeUrl, EthereumProgram, KeyPair};
+
+mod besu;
+mod error;
+mod geth;
+mod nethermind;
+mod parity;
+
+pub use self::besu::Besu;
+pub use self::error::Error;
+pub use self::geth::GoEthereum;
+pub use self::nethermind::Nethermind;
+pub use self::parity::Parity;
+
+pub type Web3 = web3::Web3<web3::transports::Ipc>;
+
+pub type EnodeFetcher = Box<dyn Future<Item = EthereumNodeUrl, Error = Error> + Send>;
+pub type PeerUpdater = Box<dyn Future<Item = bool, Error = Error> + Send>;
+pub type SyncStateFetcher = Box<dyn Future<Item = SyncState, Error = Error> + Send>;
+
+/// Client specific behaviour of an Ethereum program
+pub trait EthereumClient: Send + Sync {
+    /// file name of IPC endpoint in config directory
+    fn ipc_file_name(&self) -> &'static str;
+
+    /// file name of config file in config directory
+    fn config_file_name(&self) -> &'static str {
+        "config.toml"
+    }
+
+    /// file name of chain specification in config directory
+    fn chainspec_file_name(&self) -> &'static str {
+        "spec.json"
+    }
+
+    /// write chain specification and config file, returns path of config file
+    fn initialize(&self, launcher: &EthereumLauncher) -> Result<PathBuf, LauncherError>;
+
+    /// write chain specification only, returns path of chain specification file
+    fn write_chainspec(&self, launcher: &EthereumLauncher) -> Result<PathBuf, LauncherError>;
+
+    /// command which applies chain specification file to database before the client starts,
+    /// None if the client reads the file on start
+    fn init_chainspec_command(
+        &self,
+        _launcher: &EthereumLauncher,
+        _chainspec_file_path: &PathBuf,
+    ) -> Option<Command> {
+        None
+    }
+
+    /// import sealer key into keystore of client, returns path of key file
+    fn import_key(
+        &self,
+        launcher: &EthereumLauncher,
+        sealer_key: &KeyPair,
+        passphrase: &str,
+    ) -> Result<PathBuf, LauncherError>;
+
+    /// write devp2p node key where the client reads it, returns path of node key file or None
+    /// if the client does not take the node key
+    fn write_node_key(
+        &self,
+        _launcher: &EthereumLauncher,
+        _node_key: &KeyPair,
+    ) -> Result<Option<PathBuf>, LauncherError> {
+        Ok(None)
+    }
+
+    /// remove sealer keys and passphrase written by previous initialization
+    fn remove_keys(&self, launcher: &EthereumLauncher) -> Result<(), LauncherError>;
+
+    /// executable and its arguments
+    fn execute_command(&self, launcher: &EthereumLauncher) -> (Command, Vec<String>);
+
+    /// enode URL of running client
+    fn enode(&self, web3: &Web3) -> EnodeFetcher;
+
+    fn add_peer(&self, web3: &Web3, enode_url: &EthereumNodeUrl) -> PeerUpdater;
+
+    fn remove_peer(&self, web3: &Web3, enode_url: &EthereumNodeUrl) -> PeerUpdater;
+
+    fn sync_state(&self, web3: &Web3) -> SyncStateFetcher {
+        Box::new(web3.eth().syncing().from_err::<Error>())
+    }
+}
+
+pub fn from_program(program: EthereumProgram) -> Box<dyn EthereumClient> {
+    match program {
+        EthereumProgram::Parity => Box::new(Parity),
+        EthereumProgram::GoEthereum => Box::new(GoEthereum),
+        EthereumProgram::Nethermind => Box::new(Nethermind),
+        EthereumProgram::Besu => Box::new(Besu),
+    }
+}
+
+/// enode URL from admin_nodeInfo, which is shared by Go-Ethereum, Nethermind and Besu
+fn admin_node_enode(web3: &Web3) -> EnodeFetcher {
+    Box::new(
+        web3.transport()
+            .execute("admin_nodeInfo", vec![])
+            .from_err::<Error>()
+            .and_then(|node_info| match node_info["enode"].as_str() {
+                Some(enode_url) => Ok(EthereumNodeUrl::from_str(enode_url)?),
+                None => Err(Error::InvalidNodeInfo(node_info.to_string())),
+            }),
+    )
+}
+
+/// admin_addPeer and admin_removePeer of Nethermind and Besu return either a boolean or the peer
+fn admin_peer_result(result: &serde_json::Value) -> bool {
+    result.as_bool().unwrap_or_else(|| !result.is_null())
+}
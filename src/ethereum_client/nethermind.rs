@@ -0,0 +1,152 @@
+use ethereum_types::Address;
+use futures::Future;
+use std::path::PathBuf;
+use std::process::Command;
+use web3::Transport;
+
+use crate::ethereum_launcher::{
+    keystore, nethermind, parity, Error as LauncherError, EthereumLauncher,
+};
+use crate::primitives::{EthereumNodeUrl, KeyPair};
+use crate::utils::secret_file;
+
+use super::{EnodeFetcher, Error, EthereumClient, PeerUpdater, Web3};
+
+const NETHERMIND_EXECUTABLE_PATH: &str = "Nethermind.Runner";
+
+pub struct Nethermind;
+
+impl Nethermind {
+    fn config(
+        &self,
+        launcher: &EthereumLauncher,
+        spec_file_path: &PathBuf,
+    ) -> nethermind::NethermindConfig {
+        let config_dir = launcher.config_dir_path();
+
+        let miner_options = launcher.node_role.validator_keypair().map(|sealer_key| {
+            nethermind::NethermindMinerOptions {
+                sealer_address: Address::from(*sealer_key.public().address()),
+                sealer_passphrase_file_path: keystore::passphrase_file_path(&config_dir)
+                    .to_str()
+                    .expect("sealer passphrase file path")
+                    .to_owned(),
+            }
+        });
+
+        nethermind::NethermindConfig {
+            running_mode: launcher.running_mode,
+
+            spec_path: spec_file_path.to_str().expect("spec file path").to_owned(),
+            db_path: launcher
+                .chain_data_dir_path()
+                .to_str()
+                .expect("db directory path")
+                .to_owned(),
+            keystore_dir: keystore::keystore_directory_path(&config_dir)
+                .to_str()
+                .expect("keystore directory path")
+                .to_owned(),
+            node_role: launcher.node_role.clone(),
+            miner_options,
+
+            bootnodes: launcher.bootnodes.clone(),
+
+            ipc_path: launcher.ipc_path().to_str().expect("ipc path").to_owned(),
+            network_port: launcher.network_port,
+            http_jsonrpc_port: launcher.http_jsonrpc_port,
+            websocket_jsonrpc_port: launcher.websocket_jsonrpc_port,
+        }
+    }
+}
+
+impl EthereumClient for Nethermind {
+    fn ipc_file_name(&self) -> &'static str {
+        "nethermind.ipc"
+    }
+
+    fn config_file_name(&self) -> &'static str {
+        "config.cfg"
+    }
+
+    fn initialize(&self, launcher: &EthereumLauncher) -> Result<PathBuf, LauncherError> {
+        let spec_file_path = self.write_chainspec(launcher)?;
+
+        self.config(launcher, &spec_file_path)
+            .save_as_file(&launcher.config_file_path())
+    }
+
+    fn write_chainspec(&self, launcher: &EthereumLauncher) -> Result<PathBuf, LauncherError> {
+        // Nethermind reads chain specification in Parity format
+        parity::create_spec_file(&launcher.config_dir_path(), &launcher.chainspec)
+    }
+
+    fn import_key(
+        &self,
+        launcher: &EthereumLauncher,
+        sealer_key: &KeyPair,
+        passphrase: &str,
+    ) -> Result<PathBuf, LauncherError> {
+        let config_dir = launcher.config_dir_path();
+
+        let keystore_dir = keystore::create_keystore_directory(&config_dir)?;
+        let key_file_path = parity::create_key_file(
+            &keystore_dir,
+            sealer_key,
+            passphrase,
+            launcher.sealer_keyfile_kdf_iterations,
+        )?;
+        keystore::create_passphrase_file(&config_dir, passphrase)?;
+
+        Ok(key_file_path)
+    }
+
+    fn remove_keys(&self, launcher: &EthereumLauncher) -> Result<(), LauncherError> {
+        let config_dir = launcher.config_dir_path();
+        secret_file::remove_if_exists(&keystore::keystore_directory_path(&config_dir))?;
+        secret_file::remove_if_exists(&keystore::passphrase_file_path(&config_dir))?;
+        Ok(())
+    }
+
+    fn execute_command(&self, launcher: &EthereumLauncher) -> (Command, Vec<String>) {
+        let config_file_path = String::from(
+            launcher
+                .config_file_path()
+                .to_str()
+                .expect("config file path"),
+        );
+        (
+            Command::new(NETHERMIND_EXECUTABLE_PATH),
+            vec![format!("--config={}", config_file_path)],
+        )
+    }
+
+    fn enode(&self, web3: &Web3) -> EnodeFetcher {
+        super::admin_node_enode(web3)
+    }
+
+    /// add peer and keep it as static node
+    fn add_peer(&self, web3: &Web3, enode_url: &EthereumNodeUrl) -> PeerUpdater {
+        Box::new(
+            web3.transport()
+                .execute(
+                    "admin_addPeer",
+                    vec![json!(enode_url.to_string()), json!(true)],
+                )
+                .map(|result| super::admin_peer_result(&result))
+                .from_err::<Error>(),
+        )
+    }
+
+    fn remove_peer(&self, web3: &Web3, enode_url: &EthereumNodeUrl) -> PeerUpdater {
+        Box::new(
+            web3.transport()
+                .execute(
+                    "admin_removePeer",
+                    vec![json!(enode_url.to_string()), json!(true)],
+                )
+                .map(|result| super::admin_peer_result(&result))
+                .from_err::<Error>(),
+        )
+    }
+}
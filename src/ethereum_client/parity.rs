@@ -0,0 +1,223 @@
+use ethereum_types::Address;
+use futures::Future;
+use std::path::PathBuf;
+use std::process::Command;
+use std::str::FromStr;
+
+use crate::ethereum_launcher::{parity, Error as LauncherError, EthereumLauncher};
+use crate::primitives::{
+    EthereumNodeUrl, KeyPair, NodeRole, DEFAULT_PARITY_GAS_CAP, DEFAULT_PARITY_GAS_FLOOR_TARGET,
+};
+use crate::utils::secret_file;
+
+use super::{EnodeFetcher, Error, EthereumClient, PeerUpdater, Web3};
+
+const PARITY_EXECUTABLE_PATH: &str = "parity";
+
+const DEFAULT_TX_QUEUE_SIZE: u32 = 8192;
+const DEFAULT_TX_QUEUE_MEM_LIMIT: u32 = 4;
+const DEFAULT_TX_QUEUE_PER_SENDER: u32 = 16;
+
+pub struct Parity;
+
+impl Parity {
+    fn config(
+        &self,
+        launcher: &EthereumLauncher,
+        spec_file_path: &PathBuf,
+        reserved_peers_file_path: &PathBuf,
+    ) -> parity::ParityConfig {
+        let miner_options = match launcher.node_role {
+            NodeRole::Miner {
+                ref parity_gas_cap,
+                ref parity_gas_floor_target,
+                ..
+            } => {
+                let sealer_key = launcher
+                    .node_role
+                    .validator_keypair()
+                    .expect("index must be valid");
+
+                Some(parity::ParityMinerOptions {
+                    force_sealing: true,
+                    gas_cap: parity_gas_cap
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_PARITY_GAS_CAP.to_string()),
+                    gas_floor_target: parity_gas_floor_target
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_PARITY_GAS_FLOOR_TARGET.to_string()),
+                    sealer_address: Address::from(*sealer_key.public().address()),
+                    sealer_passphrase_file_path: parity::passphrase_file_path(
+                        &launcher.config_dir_path(),
+                    )
+                    .to_str()
+                    .expect("sealer passphrase file path")
+                    .to_owned(),
+                })
+            }
+            NodeRole::Transactor | NodeRole::Syncer => None,
+        };
+
+        parity::ParityConfig {
+            running_mode: launcher.running_mode,
+
+            miner_options,
+
+            base_path: launcher
+                .base_dir_path()
+                .to_str()
+                .expect("base directory path")
+                .to_owned(),
+            db_path: launcher
+                .chain_data_dir_path()
+                .to_str()
+                .expect("db directory path")
+                .to_owned(),
+            node_role: launcher.node_role.clone(),
+
+            identity: launcher.node_role.identity(),
+            spec_path: spec_file_path.to_str().expect("spec file path").to_owned(),
+            bootnodes: launcher.bootnodes.clone(),
+            reserved_peers_file_path: reserved_peers_file_path
+                .to_str()
+                .expect("reserved peers file")
+                .to_owned(),
+
+            ipc_path: launcher.ipc_path().to_str().expect("ipc path").to_owned(),
+            network_port: launcher.network_port,
+            http_jsonrpc_port: launcher.http_jsonrpc_port,
+            websocket_jsonrpc_port: launcher.websocket_jsonrpc_port,
+
+            tx_queue_size: launcher
+                .parity_tx_queue_size
+                .unwrap_or(DEFAULT_TX_QUEUE_SIZE),
+            tx_queue_mem_limit: launcher
+                .parity_tx_mem_limit
+                .unwrap_or(DEFAULT_TX_QUEUE_MEM_LIMIT),
+            tx_queue_per_sender: launcher
+                .parity_tx_queue_per_sender
+                .unwrap_or(DEFAULT_TX_QUEUE_PER_SENDER),
+
+            logging: launcher.parity_logging.clone(),
+        }
+    }
+}
+
+impl EthereumClient for Parity {
+    fn ipc_file_name(&self) -> &'static str {
+        "parity.ipc"
+    }
+
+    fn initialize(&self, launcher: &EthereumLauncher) -> Result<PathBuf, LauncherError> {
+        let config_dir = launcher.config_dir_path();
+
+        let spec_file_path = self.write_chainspec(launcher)?;
+        let reserved_peers_file_path =
+            parity::create_reserverd_peers_file(&config_dir, &launcher.bootnodes)?;
+
+        self.config(launcher, &spec_file_path, &reserved_peers_file_path)
+            .save_as_file(&launcher.config_file_path())
+    }
+
+    fn write_chainspec(&self, launcher: &EthereumLauncher) -> Result<PathBuf, LauncherError> {
+        parity::create_spec_file(&launcher.config_dir_path(), &launcher.chainspec)
+    }
+
+    fn import_key(
+        &self,
+        launcher: &EthereumLauncher,
+        sealer_key: &KeyPair,
+        passphrase: &str,
+    ) -> Result<PathBuf, LauncherError> {
+        let config_dir = launcher.config_dir_path();
+
+        let key_dir = parity::create_key_directory(&config_dir)?;
+        let key_file_path = parity::create_key_file(
+            &key_dir,
+            sealer_key,
+            passphrase,
+            launcher.sealer_keyfile_kdf_iterations,
+        )?;
+        parity::create_passphrase_file(&config_dir, passphrase)?;
+
+        if Command::new(PARITY_EXECUTABLE_PATH)
+            .arg(format!(
+                "--config={}",
+                launcher
+                    .config_file_path()
+                    .to_str()
+                    .expect("config file path")
+            ))
+            .arg("account")
+            .arg("import")
+            .arg(key_dir.to_str().expect("key directory"))
+            .spawn()?
+            .wait()?
+            .success()
+        {
+            Ok(key_file_path)
+        } else {
+            Err(LauncherError::FailedToImportKeyFile)
+        }
+    }
+
+    fn write_node_key(
+        &self,
+        launcher: &EthereumLauncher,
+        node_key: &KeyPair,
+    ) -> Result<Option<PathBuf>, LauncherError> {
+        parity::create_node_key_file(&launcher.base_dir_path(), node_key.secret()).map(Some)
+    }
+
+    /// keys imported into keystore of the chain in base path are removed too, keys of other
+    /// chains sharing the base path are kept
+    fn remove_keys(&self, launcher: &EthereumLauncher) -> Result<(), LauncherError> {
+        let config_dir = launcher.config_dir_path();
+        secret_file::remove_if_exists(&parity::key_directory_path(&config_dir))?;
+        secret_file::remove_if_exists(&parity::passphrase_file_path(&config_dir))?;
+        if let Some(chain_key_dir) =
+            parity::chain_key_directory_path(&launcher.base_dir_path(), &launcher.chainspec)
+        {
+            secret_file::remove_if_exists(&chain_key_dir)?;
+        }
+        Ok(())
+    }
+
+    fn execute_command(&self, launcher: &EthereumLauncher) -> (Command, Vec<String>) {
+        let config_file_path = String::from(
+            launcher
+                .config_file_path()
+                .to_str()
+                .expect("config file path"),
+        );
+        (
+            Command::new(PARITY_EXECUTABLE_PATH),
+            vec![format!("--config={}", config_file_path)],
+        )
+    }
+
+    fn enode(&self, web3: &Web3) -> EnodeFetcher {
+        Box::new(
+            web3.parity()
+                .enode()
+                .from_err::<Error>()
+                .and_then(|enode_url| Ok(EthereumNodeUrl::from_str(&enode_url)?)),
+        )
+    }
+
+    fn add_peer(&self, web3: &Web3, enode_url: &EthereumNodeUrl) -> PeerUpdater {
+        Box::new(
+            web3.parity_set()
+                .add_reserved_peer(&enode_url.to_string())
+                .from_err::<Error>(),
+        )
+    }
+
+    fn remove_peer(&self, web3: &Web3, enode_url: &EthereumNodeUrl) -> PeerUpdater {
+        Box::new(
+            web3.parity_set()
+                .remove_reserved_peer(&enode_url.to_string())
+                .from_err::<Error>(),
+        )
+    }
+}
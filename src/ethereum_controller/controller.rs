@@ -1,7 +1,10 @@
 use futures::{sync::oneshot, Async, Future, Poll};
+use serde_json::Value as JsonValue;
+use std::time::{Duration, Instant};
+use tokio::timer::Delay;
 use tokio_process::Child as ChildProcess;
 
-use crate::ethereum_launcher::EthereumLauncher;
+use crate::ethereum_launcher::{Error as LauncherError, EthereumLauncher};
 
 use super::{Error, RestartPolicy};
 
@@ -10,6 +13,15 @@ pub struct Controller {
     ethereum_launcher: EthereumLauncher,
     ethereum_process: Option<ChildProcess>,
 
+    /// chain specification which is applied after Ethereum client exits
+    pending_chainspec: Option<JsonValue>,
+
+    /// Ethereum client is stopped for pending chain specification when the delay elapses
+    restart_delay: Option<Delay>,
+
+    /// process which applies new chain specification to database, like `geth init`
+    chainspec_init_process: Option<ChildProcess>,
+
     shutdown_sender: Option<oneshot::Sender<()>>,
     shutdown_receiver: oneshot::Receiver<()>,
 }
@@ -28,6 +40,9 @@ impl Controller {
             restart_policy,
             ethereum_launcher,
             ethereum_process,
+            pending_chainspec: None,
+            restart_delay: None,
+            chainspec_init_process: None,
 
             shutdown_receiver,
             shutdown_sender: Some(shutdown_sender),
@@ -45,6 +60,19 @@ impl Controller {
         );
     }
 
+    /// chain specification of running Ethereum client
+    #[inline]
+    pub fn chainspec(&self) -> &JsonValue {
+        &self.ethereum_launcher.chainspec
+    }
+
+    /// stop Ethereum client after `delay`, new chain specification is written and the client is
+    /// started again after it exits, the database may be locked by the running client
+    pub fn update_chainspec(&mut self, chainspec: JsonValue, delay: Duration) {
+        self.pending_chainspec = Some(chainspec);
+        self.restart_delay = Some(Delay::new(Instant::now() + delay));
+    }
+
     #[allow(dead_code)]
     pub fn close(&mut self) {
         if let Some(sender) = std::mem::replace(&mut self.shutdown_sender, None) {
@@ -72,11 +100,66 @@ impl Future for Controller {
             return Ok(Async::Ready(true));
         }
 
+        let restart_due = match self.restart_delay {
+            Some(ref mut delay) => match delay.poll() {
+                Ok(Async::NotReady) => false,
+                Ok(Async::Ready(_)) | Err(_) => true,
+            },
+            None => false,
+        };
+        if restart_due {
+            self.restart_delay = None;
+            if let Some(ref mut process) = self.ethereum_process {
+                process.kill()?;
+            }
+        }
+
+        if let Some(ref mut init_process) = self.chainspec_init_process {
+            match init_process.poll()? {
+                Async::NotReady => return Ok(Async::NotReady),
+                Async::Ready(exit_status) if exit_status.success() => {
+                    self.chainspec_init_process = None;
+                    info!("Chain spec applied to database, restart Ethereum client");
+                    self.restart();
+                    return Ok(Async::NotReady);
+                }
+                Async::Ready(_) => {
+                    self.chainspec_init_process = None;
+                    return Err(LauncherError::FailedToInitializeGenesis.into());
+                }
+            }
+        }
+
         match self.ethereum_process {
             None => Ok(Async::NotReady),
             Some(ref mut process) => match process.poll() {
                 Err(err) => Err(Error::from(err)),
                 Ok(Async::NotReady) => Ok(Async::NotReady),
+                Ok(Async::Ready(_)) if self.pending_chainspec.is_some() => {
+                    let chainspec = self
+                        .pending_chainspec
+                        .take()
+                        .expect("chainspec is some; qed");
+                    let (spec_file_path, init_process) =
+                        self.ethereum_launcher.update_chainspec(chainspec)?;
+                    match init_process {
+                        Some(init_process) => {
+                            info!(
+                                "Chain spec {:?} updated, apply it to database",
+                                spec_file_path
+                            );
+                            self.chainspec_init_process = Some(init_process);
+                        }
+                        None => {
+                            info!(
+                                "Chain spec {:?} updated, restart Ethereum client",
+                                spec_file_path
+                            );
+                            self.restart();
+                        }
+                    }
+                    Ok(Async::NotReady)
+                }
                 Ok(Async::Ready(exit_status)) => match self.restart_policy {
                     RestartPolicy::No => Ok(Async::Ready(exit_status.success())),
                     RestartPolicy::OnFailure | RestartPolicy::Always => {
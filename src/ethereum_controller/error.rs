@@ -1,7 +1,12 @@
+use crate::ethereum_launcher::Error as LauncherError;
+
 #[derive(Debug, Fail)]
 pub enum Error {
     #[fail(display = "IO error: {}", _0)]
     StdIo(std::io::Error),
+
+    #[fail(display = "Ethereum launcher error: {}", _0)]
+    Launcher(LauncherError),
 }
 
 impl From<std::io::Error> for Error {
@@ -9,3 +14,9 @@ impl From<std::io::Error> for Error {
         Error::StdIo(error)
     }
 }
+
+impl From<LauncherError> for Error {
+    fn from(error: LauncherError) -> Error {
+        Error::Launcher(error)
+    }
+}
@@ -0,0 +1,120 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::primitives::EthereumNodeUrl;
+use crate::utils::{self, secret_file};
+
+use super::{Error, RunningMode};
+
+pub fn node_key_file_path(config_dir: &PathBuf) -> PathBuf {
+    let mut path = PathBuf::from(config_dir);
+    path.push("node_key");
+    path
+}
+
+/// Besu seals blocks with its node key, which is stored as hex string
+pub fn create_node_key_file(config_dir: &PathBuf, secret: &[u8]) -> Result<PathBuf, Error> {
+    let path = node_key_file_path(config_dir);
+
+    secret_file::write_private_file(&path, utils::bytes_to_0xhex(secret).as_bytes())?;
+
+    Ok(path)
+}
+
+#[derive(Debug, Clone)]
+pub struct BesuConfig {
+    pub running_mode: RunningMode,
+
+    pub network_id: u64,
+    pub data_path: String,
+    pub genesis_file_path: String,
+    pub node_key_file_path: Option<String>,
+
+    pub identity: String,
+    pub bootnodes: Vec<EthereumNodeUrl>,
+
+    pub ipc_path: String,
+    pub network_port: u16,
+    pub http_jsonrpc_port: u16,
+    pub websocket_jsonrpc_port: u16,
+}
+
+impl BesuConfig {
+    pub fn toml_config(&self) -> toml::Value {
+        let network_id = self.network_id;
+        let data_path = self.data_path.clone();
+        let genesis_file = self.genesis_file_path.clone();
+        let identity = self.identity.clone();
+        let ipc_path = self.ipc_path.clone();
+        let network_port = self.network_port;
+        let http_jsonrpc_port = self.http_jsonrpc_port;
+        let websocket_jsonrpc_port = self.websocket_jsonrpc_port;
+
+        let (http_apis, ws_apis, ipc_apis) = {
+            let apis = match self.running_mode {
+                RunningMode::Production => vec!["ADMIN", "ETH", "NET", "WEB3"],
+                RunningMode::Development => vec![
+                    "ADMIN", "CLIQUE", "DEBUG", "ETH", "IBFT", "MINER", "NET", "TXPOOL", "WEB3",
+                ],
+            };
+            (apis.clone(), apis.clone(), apis)
+        };
+
+        let bootnodes: Vec<_> = self
+            .bootnodes
+            .iter()
+            .map(EthereumNodeUrl::to_string)
+            .collect();
+
+        let mut config = toml! {
+            data-path = data_path
+            genesis-file = genesis_file
+            network-id = network_id
+            identity = identity
+
+            p2p-port = network_port
+            bootnodes = bootnodes
+            max-peers = 128
+
+            rpc-http-enabled = true
+            rpc-http-host = "0.0.0.0"
+            rpc-http-port = http_jsonrpc_port
+            rpc-http-api = http_apis
+            rpc-http-cors-origins = ["*"]
+            host-allowlist = ["*"]
+
+            rpc-ws-enabled = true
+            rpc-ws-host = "0.0.0.0"
+            rpc-ws-port = websocket_jsonrpc_port
+            rpc-ws-api = ws_apis
+
+            Xrpc-ipc-enabled = true
+            Xrpc-ipc-path = ipc_path
+            Xrpc-ipc-apis = ipc_apis
+        };
+
+        if let Some(ref node_key_file_path) = self.node_key_file_path {
+            config
+                .as_table_mut()
+                .expect("config is a table; qed")
+                .insert(
+                    "node-private-key-file".to_owned(),
+                    toml::Value::String(node_key_file_path.clone()),
+                );
+        }
+
+        config
+    }
+
+    pub fn save_as_file(&self, config_file_path: &PathBuf) -> Result<PathBuf, Error> {
+        let config = self.toml_config();
+        let data = toml::to_string(&config).expect("config is serializable; qed");
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(config_file_path)?
+            .write_all(data.as_bytes())?;
+        Ok(config_file_path.clone())
+    }
+}
@@ -17,6 +17,12 @@ pub enum Error {
 
     #[fail(display = "Failed to import key file")]
     FailedToImportKeyFile,
+
+    #[fail(display = "Failed to initialize genesis block")]
+    FailedToInitializeGenesis,
+
+    #[fail(display = "Network ID is missing")]
+    MissingNetworkId,
 }
 
 impl From<std::io::Error> for Error {
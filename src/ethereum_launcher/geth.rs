@@ -1,15 +1,176 @@
+use ethereum_types::Address;
+use std::io::Write;
 use std::path::PathBuf;
 
-use super::Error;
+use crate::primitives::{EthereumNodeUrl, NodeRole};
+use crate::utils::secret_file;
+
+use super::{Error, RunningMode};
+
+/// Geth reads node key from `geth/nodekey` in data directory
+pub fn create_node_key_file(data_dir: &PathBuf, node_key: &[u8]) -> Result<PathBuf, Error> {
+    let mut path = PathBuf::from(data_dir);
+    path.push("geth");
+    secret_file::create_private_dir(&path)?;
+    path.push("nodekey");
+
+    secret_file::write_private_file(
+        &path,
+        &crate::utils::bytes_to_0xhex(node_key).as_bytes()[2..],
+    )?;
 
-#[allow(dead_code)]
-pub fn create_genesis_file() -> Result<PathBuf, Error> {
-    let path = PathBuf::new();
     Ok(path)
 }
 
-#[allow(dead_code)]
-pub fn create_spec_file() -> Result<PathBuf, Error> {
-    let path = PathBuf::new();
+pub fn create_genesis_file(
+    config_dir: &PathBuf,
+    genesis: &serde_json::Value,
+) -> Result<PathBuf, Error> {
+    let mut path = PathBuf::from(config_dir);
+    path.push("genesis.json");
+
+    serde_json::to_writer(
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path.clone())?,
+        &genesis,
+    )?;
+
     Ok(path)
 }
+
+#[derive(Debug, Clone)]
+pub struct GethMinerOptions {
+    pub sealer_address: Address,
+    pub sealer_passphrase_file_path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct GethConfig {
+    pub running_mode: RunningMode,
+
+    pub network_id: u64,
+    pub data_dir: String,
+    pub keystore_dir: String,
+    pub node_role: NodeRole,
+
+    pub identity: String,
+
+    pub bootnodes: Vec<EthereumNodeUrl>,
+
+    pub ipc_path: String,
+    pub network_port: u16,
+    pub http_jsonrpc_port: u16,
+    pub websocket_jsonrpc_port: u16,
+}
+
+impl GethConfig {
+    pub fn toml_config(&self) -> toml::Value {
+        let network_id = self.network_id;
+        let data_dir = self.data_dir.clone();
+        let keystore_dir = self.keystore_dir.clone();
+        let identity = self.identity.clone();
+        let ipc_path = self.ipc_path.clone();
+        let http_jsonrpc_port = self.http_jsonrpc_port;
+        let websocket_jsonrpc_port = self.websocket_jsonrpc_port;
+        let listen_addr = format!(":{}", self.network_port);
+        let no_pruning = match self.node_role {
+            NodeRole::Miner { .. } | NodeRole::Transactor => false,
+            NodeRole::Syncer => true,
+        };
+
+        let (http_apis, ws_apis) = {
+            let apis = match self.running_mode {
+                RunningMode::Production => vec!["eth", "net", "web3"],
+                RunningMode::Development => {
+                    vec![
+                        "admin", "debug", "eth", "miner", "net", "personal", "txpool", "web3",
+                    ]
+                }
+            };
+            (apis.clone(), apis)
+        };
+
+        let bootnodes: Vec<_> = self
+            .bootnodes
+            .iter()
+            .map(EthereumNodeUrl::to_string)
+            .collect();
+        let static_nodes = bootnodes.clone();
+
+        let mut config = toml! {
+            [Eth]
+            NetworkId = network_id
+            SyncMode = "full"
+            NoPruning = no_pruning
+
+            [Node]
+            DataDir = data_dir
+            KeyStoreDir = keystore_dir
+            IPCPath = ipc_path
+            UserIdent = identity
+            HTTPHost = "0.0.0.0"
+            HTTPPort = http_jsonrpc_port
+            HTTPCors = ["*"]
+            HTTPVirtualHosts = ["*"]
+            HTTPModules = http_apis
+            WSHost = "0.0.0.0"
+            WSPort = websocket_jsonrpc_port
+            WSOrigins = ["*"]
+            WSModules = ws_apis
+        };
+
+        let p2p = toml! {
+            MaxPeers = 128
+            MaxPendingPeers = 32
+            NoDiscovery = false
+            BootstrapNodes = bootnodes
+            StaticNodes = static_nodes
+            ListenAddr = listen_addr
+        };
+
+        config
+            .get_mut("Node")
+            .and_then(toml::Value::as_table_mut)
+            .expect("Node is a table; qed")
+            .insert("P2P".to_owned(), p2p);
+
+        config
+    }
+
+    pub fn save_as_file(&self, config_file_path: &PathBuf) -> Result<PathBuf, Error> {
+        let config = self.toml_config();
+        let data = toml::to_string(&config).expect("config is serializable; qed");
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(config_file_path)?
+            .write_all(data.as_bytes())?;
+        Ok(config_file_path.clone())
+    }
+}
+
+/// command line arguments which are not expressible in the config file
+pub fn command_args(
+    config_file_path: &str,
+    miner_options: Option<&GethMinerOptions>,
+) -> Vec<String> {
+    let mut args = vec![format!("--config={}", config_file_path)];
+
+    if let Some(options) = miner_options {
+        let sealer_address = format!("{:?}", options.sealer_address);
+        args.push(format!("--unlock={}", sealer_address));
+        args.push(format!(
+            "--password={}",
+            options.sealer_passphrase_file_path
+        ));
+        args.push("--allow-insecure-unlock".to_owned());
+        args.push("--mine".to_owned());
+        args.push(format!("--miner.etherbase={}", sealer_address));
+    }
+
+    args
+}
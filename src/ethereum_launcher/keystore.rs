@@ -0,0 +1,34 @@
+//! Keystore directory and sealer passphrase file of Go-Ethereum and Nethermind
+
+use std::path::PathBuf;
+
+use crate::utils::secret_file;
+
+use super::Error;
+
+pub fn keystore_directory_path(config_dir_path: &PathBuf) -> PathBuf {
+    let mut path = PathBuf::from(config_dir_path);
+    path.push("keystore");
+    path
+}
+
+pub fn create_keystore_directory(config_dir_path: &PathBuf) -> Result<PathBuf, Error> {
+    let path = keystore_directory_path(config_dir_path);
+
+    secret_file::create_private_dir(&path)?;
+    Ok(path)
+}
+
+pub fn passphrase_file_path(config_dir: &PathBuf) -> PathBuf {
+    let mut path = PathBuf::from(config_dir);
+    path.push("sealer_passphrase");
+    path
+}
+
+pub fn create_passphrase_file(config_dir: &PathBuf, passphrase: &str) -> Result<PathBuf, Error> {
+    let path = passphrase_file_path(config_dir);
+
+    secret_file::write_private_file(&path, passphrase.as_bytes())?;
+
+    Ok(path)
+}
@@ -4,24 +4,23 @@ use std::path::PathBuf;
 use std::process::Command;
 use tokio_process::{Child as ChildProcess, CommandExt};
 
+use crate::ethereum_client::{self, EthereumClient};
 use crate::primitives::{
-    EthereumNodeUrl, EthereumProgram, NodeRole, DEFAULT_PARITY_GAS_CAP,
-    DEFAULT_PARITY_GAS_FLOOR_TARGET,
+    node_id, EthereumNodeUrl, EthereumProgram, KeyPair, NodeKeySeed, NodeRole,
 };
+use crate::utils::secret_file;
 
+pub mod besu;
 mod error;
-mod geth;
-mod parity;
+pub mod geth;
+pub mod keystore;
+pub mod nethermind;
+pub mod parity;
 
 pub use self::error::Error;
 
-const PARITY_EXECUTABLE_PATH: &str = "parity";
-const GETH_EXECUTABLE_PATH: &str = "geth";
-const DEFAULT_SEALER_KEYFILE_PASSPHRASE: &str = "0123456789";
-
-const DEFAULT_TX_QUEUE_SIZE: u32 = 8192;
-const DEFAULT_TX_QUEUE_MEM_LIMIT: u32 = 4;
-const DEFAULT_TX_QUEUE_PER_SENDER: u32 = 16;
+/// length of random passphrase of sealer keystore in bytes
+const SEALER_KEYFILE_PASSPHRASE_LENGTH: usize = 32;
 
 #[derive(Debug, Clone, Copy)]
 pub enum RunningMode {
@@ -45,6 +44,9 @@ pub struct EthereumLauncher {
     pub program: EthereumProgram,
     pub chainspec: JsonValue,
 
+    /// network ID of devp2p, required by Go-Ethereum and Besu whose genesis.json does not carry it
+    pub network_id: Option<u64>,
+
     pub config_file_path: Option<String>,
     pub running_mode: RunningMode,
 
@@ -62,6 +64,15 @@ pub struct EthereumLauncher {
     pub parity_tx_queue_per_sender: Option<u32>,
 
     pub parity_logging: Option<String>,
+
+    /// file of passphrase of sealer keystore, a random passphrase is used if absent
+    pub sealer_keyfile_passphrase_file: Option<String>,
+    pub sealer_keyfile_kdf_iterations: u32,
+
+    /// seed and name of node which its devp2p node key is derived from, the client generates a
+    /// random node key if either is absent
+    pub node_key_seed: Option<NodeKeySeed>,
+    pub node_name: Option<String>,
 }
 
 impl EthereumLauncher {
@@ -92,10 +103,7 @@ impl EthereumLauncher {
             Some(ipc_path) => PathBuf::from(ipc_path.clone()),
             None => {
                 let mut path = self.config_dir_path();
-                path.push(match self.program {
-                    EthereumProgram::Parity => "parity.ipc",
-                    EthereumProgram::GoEthereum => "geth.ipc",
-                });
+                path.push(self.client().ipc_file_name());
                 path
             }
         }
@@ -106,188 +114,110 @@ impl EthereumLauncher {
             Some(config_file_path) => PathBuf::from(config_file_path),
             None => {
                 let mut path_buf = self.config_dir_path();
-                path_buf.push("config.toml");
+                path_buf.push(self.client().config_file_name());
                 path_buf
             }
         }
     }
 
+    #[inline]
+    pub fn client(&self) -> Box<dyn EthereumClient> {
+        ethereum_client::from_program(self.program)
+    }
+
     pub fn initialize(&self) -> Result<String, Error> {
-        match self.program {
-            EthereumProgram::Parity => self.initialize_parity(),
-            EthereumProgram::GoEthereum => self.initialize_geth(),
+        std::fs::create_dir_all(self.config_dir_path())?;
+        std::fs::create_dir_all(self.chain_data_dir_path())?;
+
+        let client = self.client();
+        let config_file_path = client.initialize(self)?;
+
+        // keys of previous initialization can not be unlocked with a new random passphrase
+        client.remove_keys(self)?;
+        if let Some(sealer_key) = self.node_role.validator_keypair() {
+            let passphrase = self.sealer_keyfile_passphrase()?;
+            let sealer_address = Address::from(*sealer_key.public().address());
+
+            let key_file_path = client.import_key(self, &sealer_key, &passphrase)?;
+
+            info!(target: "launcher", "create key file {:?} for {:?}",
+                  key_file_path, sealer_address);
+
+            // the key is imported anyway since a later chain spec revision may add the sealer
+            match self.node_role.activation_block() {
+                Some(0) => {}
+                Some(block) => {
+                    info!(target: "launcher", "{:?} starts sealing at block {}",
+                          sealer_address, block);
+                }
+                None => {
+                    warn!(target: "launcher", "{:?} is not a validator, blocks are not sealed",
+                          sealer_address);
+                }
+            }
         }
-    }
 
-    fn initialize_parity(&self) -> Result<String, Error> {
-        let config_dir = self.config_dir_path();
-        std::fs::create_dir_all(config_dir.clone())?;
-
-        let db_path = self.chain_data_dir_path();
-        std::fs::create_dir_all(db_path.clone())?;
-
-        let spec_file_path = parity::create_spec_file(&config_dir, &self.chainspec)?;
-        let reserved_peers_file_path =
-            parity::create_reserverd_peers_file(&config_dir, &self.bootnodes)?;
-
-        match self.node_role.clone() {
-            NodeRole::Miner {
-                parity_gas_cap,
-                parity_gas_floor_target,
-                ..
-            } => {
-                let passphrase = String::from(DEFAULT_SEALER_KEYFILE_PASSPHRASE);
-
-                let sealer_key = self
-                    .node_role
-                    .validator_keypair()
-                    .expect("index must be valid");
-                let sealer_address = Address::from(*sealer_key.public().address());
-
-                let key_dir = parity::create_key_directory(&config_dir)?;
-                let key_file_path = parity::create_key_file(&key_dir, &sealer_key, &passphrase)?;
-
-                info!(target: "launcher", "create key file {:?} for {:?}",
-                      key_file_path, sealer_address);
-
-                let sealer_password_file_path =
-                    parity::create_passphrase_file(&config_dir, &passphrase)?;
-
-                let config_file_path: String = {
-                    let config = parity::ParityConfig {
-                        running_mode: self.running_mode,
-
-                        miner_options: Some(parity::ParityMinerOptions {
-                            force_sealing: true,
-                            gas_cap: parity_gas_cap
-                                .unwrap_or_else(|| DEFAULT_PARITY_GAS_CAP.to_string()),
-                            gas_floor_target: parity_gas_floor_target
-                                .unwrap_or_else(|| DEFAULT_PARITY_GAS_FLOOR_TARGET.to_string()),
-                            sealer_address,
-                            sealer_passphrase_file_path: sealer_password_file_path
-                                .to_str()
-                                .expect("sealer passphrase file path")
-                                .to_owned(),
-                        }),
-
-                        base_path: self
-                            .base_dir_path()
-                            .to_str()
-                            .expect("base directory path")
-                            .to_owned(),
-                        db_path: db_path.to_str().expect("db directory path").to_owned(),
-                        node_role: self.node_role.clone(),
-
-                        identity: self.node_role.identity(),
-                        spec_path: spec_file_path.to_str().expect("spec file path").to_owned(),
-                        bootnodes: self.bootnodes.clone(),
-                        reserved_peers_file_path: reserved_peers_file_path
-                            .to_str()
-                            .expect("reserved peers file")
-                            .to_owned(),
-
-                        ipc_path: self.ipc_path().to_str().expect("ipc path").to_owned(),
-                        network_port: self.network_port,
-                        http_jsonrpc_port: self.http_jsonrpc_port,
-                        websocket_jsonrpc_port: self.websocket_jsonrpc_port,
-
-                        tx_queue_size: self.parity_tx_queue_size.unwrap_or(DEFAULT_TX_QUEUE_SIZE),
-                        tx_queue_mem_limit: self
-                            .parity_tx_mem_limit
-                            .unwrap_or(DEFAULT_TX_QUEUE_MEM_LIMIT),
-                        tx_queue_per_sender: self
-                            .parity_tx_queue_per_sender
-                            .unwrap_or(DEFAULT_TX_QUEUE_PER_SENDER),
-
-                        logging: self.parity_logging.clone(),
-                    };
-
-                    config
-                        .save_as_file(&self.config_file_path())?
-                        .to_str()
-                        .expect("config file path")
-                        .into()
-                };
-
-                if Command::new(PARITY_EXECUTABLE_PATH)
-                    .arg(format!("--config={}", config_file_path))
-                    .arg("account")
-                    .arg("import")
-                    .arg(key_dir.to_str().expect("key directory"))
-                    .spawn()?
-                    .wait()?
-                    .success()
-                {
-                    Ok(config_file_path)
-                } else {
-                    Err(Error::FailedToImportKeyFile)
+        if let Some(node_key) = self.node_key() {
+            match client.write_node_key(self, &node_key)? {
+                Some(node_key_file_path) => {
+                    info!(target: "launcher", "create node key file {:?} for node ID {}",
+                          node_key_file_path, node_id(&node_key));
+                }
+                None => {
+                    warn!(target: "launcher", "derived node key is not used by {:?}",
+                          self.program);
                 }
             }
-            NodeRole::Transactor | NodeRole::Syncer => {
-                let config_file_path: String = {
-                    let config = parity::ParityConfig {
-                        running_mode: self.running_mode,
-
-                        miner_options: None,
-
-                        base_path: self
-                            .base_dir_path()
-                            .to_str()
-                            .expect("base directory path")
-                            .to_owned(),
-                        db_path: db_path.to_str().expect("db directory path").to_owned(),
-                        node_role: self.node_role.clone(),
-
-                        identity: self.node_role.identity(),
-                        spec_path: spec_file_path.to_str().expect("spec file path").to_owned(),
-                        bootnodes: self.bootnodes.clone(),
-                        reserved_peers_file_path: reserved_peers_file_path
-                            .to_str()
-                            .expect("reserved peers file")
-                            .to_owned(),
-
-                        ipc_path: self.ipc_path().to_str().expect("ipc path").to_owned(),
-                        network_port: self.network_port,
-                        http_jsonrpc_port: self.http_jsonrpc_port,
-                        websocket_jsonrpc_port: self.websocket_jsonrpc_port,
-
-                        tx_queue_size: self.parity_tx_queue_size.unwrap_or(DEFAULT_TX_QUEUE_SIZE),
-                        tx_queue_mem_limit: self
-                            .parity_tx_mem_limit
-                            .unwrap_or(DEFAULT_TX_QUEUE_MEM_LIMIT),
-                        tx_queue_per_sender: self
-                            .parity_tx_queue_per_sender
-                            .unwrap_or(DEFAULT_TX_QUEUE_PER_SENDER),
-
-                        logging: self.parity_logging.clone(),
-                    };
-
-                    config
-                        .save_as_file(&self.config_file_path())?
-                        .to_str()
-                        .expect("config file path")
-                        .into()
-                };
-
-                Ok(config_file_path)
+        }
+
+        Ok(config_file_path
+            .to_str()
+            .expect("config file path")
+            .to_owned())
+    }
+
+    pub fn network_id(&self) -> Result<u64, Error> {
+        self.network_id.ok_or(Error::MissingNetworkId)
+    }
+
+    /// devp2p node key derived from node key seed and node name
+    pub fn node_key(&self) -> Option<KeyPair> {
+        match (&self.node_key_seed, &self.node_name) {
+            (Some(seed), Some(name)) => Some(seed.node_key(name)),
+            _ => None,
+        }
+    }
+
+    /// passphrase of sealer keystore from passphrase file, or a random one
+    fn sealer_keyfile_passphrase(&self) -> Result<String, Error> {
+        match self.sealer_keyfile_passphrase_file {
+            Some(ref file_path) => Ok(std::fs::read_to_string(file_path)?.trim_end().to_owned()),
+            None => {
+                let passphrase = secret_file::random_bytes(SEALER_KEYFILE_PASSPHRASE_LENGTH)?;
+                Ok(crate::utils::bytes_to_0xhex(&passphrase)[2..].to_owned())
             }
         }
     }
 
-    fn initialize_geth(&self) -> Result<String, Error> {
-        unimplemented!();
+    /// replace chain specification, the running client has to be restarted to apply it, returns
+    /// path of chain specification file and the process which applies it to database if any
+    pub fn update_chainspec(
+        &mut self,
+        chainspec: JsonValue,
+    ) -> Result<(PathBuf, Option<ChildProcess>), Error> {
+        self.chainspec = chainspec;
+
+        let client = self.client();
+        let chainspec_file_path = client.write_chainspec(self)?;
+        let init_process = match client.init_chainspec_command(self, &chainspec_file_path) {
+            Some(mut command) => Some(command.spawn_async()?),
+            None => None,
+        };
+        Ok((chainspec_file_path, init_process))
     }
 
     fn execute_command(&self) -> (Command, Vec<String>) {
-        let config_file_path =
-            String::from(self.config_file_path().to_str().expect("config file path"));
-        match self.program {
-            EthereumProgram::Parity => (
-                Command::new(PARITY_EXECUTABLE_PATH),
-                vec![format!("--config={}", config_file_path)],
-            ),
-            EthereumProgram::GoEthereum => (Command::new(GETH_EXECUTABLE_PATH), vec![]),
-        }
+        self.client().execute_command(self)
     }
 
     pub fn execute_async(&self) -> Result<ChildProcess, std::io::Error> {
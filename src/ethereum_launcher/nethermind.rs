@@ -0,0 +1,110 @@
+use ethereum_types::Address;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::primitives::{EthereumNodeUrl, NodeRole};
+
+use super::{Error, RunningMode};
+
+#[derive(Debug, Clone)]
+pub struct NethermindMinerOptions {
+    pub sealer_address: Address,
+    pub sealer_passphrase_file_path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NethermindConfig {
+    pub running_mode: RunningMode,
+
+    pub spec_path: String,
+    pub db_path: String,
+    pub keystore_dir: String,
+    pub node_role: NodeRole,
+    pub miner_options: Option<NethermindMinerOptions>,
+
+    pub bootnodes: Vec<EthereumNodeUrl>,
+
+    pub ipc_path: String,
+    pub network_port: u16,
+    pub http_jsonrpc_port: u16,
+    pub websocket_jsonrpc_port: u16,
+}
+
+impl NethermindConfig {
+    pub fn json_config(&self) -> serde_json::Value {
+        let bootnodes = self
+            .bootnodes
+            .iter()
+            .map(EthereumNodeUrl::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let modules = match self.running_mode {
+            RunningMode::Production => vec!["Eth", "Net", "Web3", "Admin"],
+            RunningMode::Development => vec![
+                "Admin", "Debug", "Eth", "Net", "Parity", "Personal", "TxPool", "Web3",
+            ],
+        };
+
+        let (is_mining, key_store) = match self.miner_options {
+            Some(ref options) => {
+                let sealer_address = format!("{:?}", options.sealer_address);
+                (
+                    true,
+                    json!({
+                        "KeyStoreDirectory": self.keystore_dir,
+                        "PasswordFiles": [options.sealer_passphrase_file_path],
+                        "UnlockAccounts": [sealer_address],
+                        "BlockAuthorAccount": sealer_address,
+                    }),
+                )
+            }
+            None => (false, json!({ "KeyStoreDirectory": self.keystore_dir })),
+        };
+
+        let mut config = json!({
+            "Init": {
+                "ChainSpecPath": self.spec_path,
+                "BaseDbPath": self.db_path,
+                "IsMining": is_mining,
+                "StoreReceipts": true,
+            },
+            "Network": {
+                "P2PPort": self.network_port,
+                "DiscoveryPort": self.network_port,
+                "StaticPeers": bootnodes,
+                "MaxActivePeers": 128,
+            },
+            "Discovery": {
+                "Bootnodes": bootnodes,
+            },
+            "JsonRpc": {
+                "Enabled": true,
+                "Host": "0.0.0.0",
+                "Port": self.http_jsonrpc_port,
+                "WebSocketsPort": self.websocket_jsonrpc_port,
+                "EnabledModules": modules,
+                "IpcUnixDomainSocketPath": self.ipc_path,
+            },
+            "KeyStore": key_store,
+        });
+
+        if let NodeRole::Syncer = self.node_role {
+            config["Pruning"] = json!({ "Mode": "None" });
+        }
+
+        config
+    }
+
+    pub fn save_as_file(&self, config_file_path: &PathBuf) -> Result<PathBuf, Error> {
+        let config = self.json_config();
+        let data = serde_json::to_string_pretty(&config)?;
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(config_file_path)?
+            .write_all(data.as_bytes())?;
+        Ok(config_file_path.clone())
+    }
+}
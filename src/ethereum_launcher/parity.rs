@@ -1,61 +1,90 @@
 use ethereum_types::{Address, U256};
-use ethsign::{keyfile::KeyFile, Protected, SecretKey};
+use ethsign::SecretKey;
 use std::io::Write;
 use std::path::PathBuf;
 
-use crate::primitives::{EthereumNodeUrl, NodeRole};
+use crate::primitives::{key_file, EthereumNodeUrl, NodeRole};
+use crate::utils::{self, secret_file};
 
 use super::{Error, RunningMode};
 
-pub fn create_key_directory(config_dir_path: &PathBuf) -> Result<PathBuf, Error> {
+pub fn key_directory_path(config_dir_path: &PathBuf) -> PathBuf {
     let mut path = PathBuf::from(config_dir_path);
     path.push("keys");
+    path
+}
+
+/// Parity imports keys into `keys/<data directory>` in base path, the data directory of a chain
+/// is `dataDir` of spec.json and falls back to its name, None if it is not a plain directory name
+pub fn chain_key_directory_path(
+    base_dir: &PathBuf,
+    chainspec: &serde_json::Value,
+) -> Option<PathBuf> {
+    let data_dir = chainspec["dataDir"]
+        .as_str()
+        .or_else(|| chainspec["name"].as_str())
+        .filter(|dir| !dir.is_empty() && *dir != "." && *dir != ".." && !dir.contains('/'))?;
+
+    let mut path = PathBuf::from(base_dir);
+    path.push("keys");
+    path.push(data_dir);
+    Some(path)
+}
 
-    std::fs::create_dir_all(path.clone())?;
+pub fn create_key_directory(config_dir_path: &PathBuf) -> Result<PathBuf, Error> {
+    let path = key_directory_path(config_dir_path);
+
+    secret_file::create_private_dir(&path)?;
     Ok(path)
 }
 
+/// write V3 keystore of sealer key with a random id, encrypted by PBKDF2 of `kdf_iterations`
+/// rounds
 pub fn create_key_file(
     key_dir_path: &PathBuf,
     private_key: &SecretKey,
     passphrase: &str,
+    kdf_iterations: u32,
 ) -> Result<PathBuf, Error> {
-    let passphrase = Protected::from(passphrase.as_bytes());
-    let keyfile = KeyFile {
-        id: "6845de15-c9d1-4af6-8386-da01205284d7".to_owned(),
-        version: 3,
-        crypto: private_key.to_crypto(
-            &passphrase,
-            std::num::NonZeroU32::new(1024).expect("1024 is none zero; qed"),
-        )?,
-        address: Some(ethsign::keyfile::Bytes(
-            private_key.public().address().to_vec(),
-        )),
-    };
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&secret_file::random_bytes(16)?);
+    let keyfile = key_file(
+        private_key,
+        passphrase,
+        utils::uuid_from_bytes(&id),
+        kdf_iterations,
+    )?;
 
     let mut path = PathBuf::from(key_dir_path);
     path.push("signer_keyfile.json");
 
-    serde_json::to_writer(
-        std::fs::OpenOptions::new()
-            .write(true)
-            .create(true)
-            .open(path.clone())?,
-        &keyfile,
-    )?;
+    secret_file::write_private_file(&path, &serde_json::to_vec(&keyfile)?)?;
 
     Ok(path)
 }
 
-pub fn create_passphrase_file(config_dir: &PathBuf, passphrase: &str) -> Result<PathBuf, Error> {
+/// Parity reads node key from `network/key` in base path
+pub fn create_node_key_file(base_dir: &PathBuf, node_key: &[u8]) -> Result<PathBuf, Error> {
+    let mut path = PathBuf::from(base_dir);
+    path.push("network");
+    secret_file::create_private_dir(&path)?;
+    path.push("key");
+
+    secret_file::write_private_file(&path, &utils::bytes_to_0xhex(node_key).as_bytes()[2..])?;
+
+    Ok(path)
+}
+
+pub fn passphrase_file_path(config_dir: &PathBuf) -> PathBuf {
     let mut path = PathBuf::from(config_dir);
     path.push("sealer_passphrase");
+    path
+}
 
-    std::fs::OpenOptions::new()
-        .write(true)
-        .create(true)
-        .open(path.clone())?
-        .write_all(passphrase.as_bytes())?;
+pub fn create_passphrase_file(config_dir: &PathBuf, passphrase: &str) -> Result<PathBuf, Error> {
+    let path = passphrase_file_path(config_dir);
+
+    secret_file::write_private_file(&path, passphrase.as_bytes())?;
 
     Ok(path)
 }
@@ -93,6 +122,7 @@ pub fn create_spec_file(
         std::fs::OpenOptions::new()
             .write(true)
             .create(true)
+            .truncate(true)
             .open(path.clone())?,
         &chainspec,
     )?;
@@ -21,6 +21,7 @@ extern crate structopt;
 
 mod bootnode;
 mod commands;
+mod ethereum_client;
 mod ethereum_controller;
 mod ethereum_launcher;
 mod network_keeper;
@@ -0,0 +1,134 @@
+use futures::{sync::mpsc, Async, Future, Poll, Stream};
+use serde_json::Value as JsonValue;
+
+use super::{BootnodeClient, Error};
+
+type RevisionFetcher = Box<dyn Future<Item = Option<(u64, JsonValue)>, Error = Error> + Send>;
+
+pub struct ChainSpecWatcher {
+    inner: Inner,
+
+    event_receiver: mpsc::UnboundedReceiver<()>,
+    event_sender: mpsc::UnboundedSender<()>,
+
+    network_name: String,
+    bootnode_client: BootnodeClient,
+
+    revision: u64,
+    updated_chainspec: Option<(u64, JsonValue)>,
+}
+
+impl ChainSpecWatcher {
+    pub fn new(
+        network_name: String,
+        bootnode_client: BootnodeClient,
+        revision: u64,
+    ) -> ChainSpecWatcher {
+        let (event_sender, event_receiver) = mpsc::unbounded();
+        ChainSpecWatcher {
+            inner: Inner::Idle,
+            event_receiver,
+            event_sender,
+            network_name,
+            bootnode_client,
+            revision,
+            updated_chainspec: None,
+        }
+    }
+
+    pub fn check(&self) {
+        self.event_sender
+            .unbounded_send(())
+            .expect("receiver always existed; qed");
+    }
+
+    /// greater revision of chain specification which is not taken yet
+    pub fn take_updated_chainspec(&mut self) -> Option<(u64, JsonValue)> {
+        self.updated_chainspec.take()
+    }
+}
+
+impl Future for ChainSpecWatcher {
+    type Item = ();
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            self.inner = match self.inner {
+                Inner::Idle => match self.event_receiver.poll() {
+                    Ok(Async::Ready(Some(_))) => Inner::fetch_revision(
+                        &self.bootnode_client,
+                        &self.network_name,
+                        self.revision,
+                    ),
+                    _ => return Ok(Async::NotReady),
+                },
+                Inner::FetchingRevision { ref mut fetcher } => match fetcher.poll() {
+                    Ok(Async::NotReady) => return Ok(Async::NotReady),
+                    Ok(Async::Ready(Some((revision, chainspec)))) => {
+                        info!(
+                            "ChainSpec Watcher: chain spec revision {} fetched, current revision: {}",
+                            revision, self.revision
+                        );
+                        self.revision = revision;
+                        self.updated_chainspec = Some((revision, chainspec));
+                        Inner::Idle
+                    }
+                    Ok(Async::Ready(None)) => Inner::Idle,
+                    Err(err) => {
+                        warn!(
+                            "ChainSpec Watcher: Failed to fetch chain spec revision, error: {:?}",
+                            err
+                        );
+                        Inner::Idle
+                    }
+                },
+            }
+        }
+    }
+}
+
+enum Inner {
+    Idle,
+    FetchingRevision { fetcher: RevisionFetcher },
+}
+
+impl Inner {
+    /// fetch chain specification only if bootnode serves a revision greater than the current one
+    fn fetch_revision(
+        bootnode_client: &BootnodeClient,
+        network_name: &str,
+        current_revision: u64,
+    ) -> Inner {
+        let fetcher = {
+            let bootnode_client = bootnode_client.clone();
+            let network_name = network_name.to_owned();
+
+            bootnode_client
+                .fetch_system_info(&network_name)
+                .from_err::<Error>()
+                .and_then(move |info| -> RevisionFetcher {
+                    if info.chainspec_revision <= current_revision {
+                        return Box::new(futures::future::ok(None));
+                    }
+
+                    Box::new(
+                        bootnode_client
+                            .fetch_chainspec_revision(&network_name)
+                            .from_err::<Error>()
+                            .map(move |(revision, chainspec)| {
+                                if revision > current_revision && !chainspec.is_null() {
+                                    Some((revision, chainspec))
+                                } else {
+                                    None
+                                }
+                            }),
+                    )
+                })
+        };
+
+        Inner::FetchingRevision {
+            fetcher: Box::new(fetcher),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::bootnode::BootnodeClientError;
+use crate::ethereum_client::Error as EthereumClientError;
 use crate::primitives::EthereumNodeUrlError;
 
 #[derive(Debug, Fail)]
@@ -15,6 +16,9 @@ pub enum Error {
 
     #[fail(display = "Unable to register Ethereum node info")]
     UnableToRegisterEthereumNodeInfo,
+
+    #[fail(display = "EthereumClient error: {}", _0)]
+    EthereumClient(EthereumClientError),
 }
 
 impl From<web3::Error> for Error {
@@ -29,6 +33,12 @@ impl From<BootnodeClientError> for Error {
     }
 }
 
+impl From<EthereumClientError> for Error {
+    fn from(error: EthereumClientError) -> Error {
+        Error::EthereumClient(error)
+    }
+}
+
 impl From<EthereumNodeUrlError> for Error {
     fn from(error: EthereumNodeUrlError) -> Error {
         Error::EthereumNodeUrl(error)
@@ -1,12 +1,14 @@
 use futures::{sync::mpsc, Async, Future, Poll, Stream};
 use std::collections::HashSet;
 
+use crate::ethereum_client::{self, EthereumClient};
 use crate::primitives::{EthereumNodeUrl, EthereumProgram};
 
 use super::{BootnodeClient, Error, Web3};
 
 type PeerFetcher = Box<dyn Future<Item = Vec<EthereumNodeUrl>, Error = Error> + Send>;
-type PeerImporter = Box<dyn Future<Item = usize, Error = ()> + Send>;
+/// numbers of imported and removed peers
+type PeerImporter = Box<dyn Future<Item = (usize, usize), Error = ()> + Send>;
 
 struct PeerCache {
     cache: HashSet<EthereumNodeUrl>,
@@ -49,6 +51,23 @@ impl PeerCache {
 
         new_peers
     }
+
+    /// drop and return cached peers which the bootnode does not serve anymore
+    fn expire(&mut self, peers: &[EthereumNodeUrl]) -> Vec<EthereumNodeUrl> {
+        let peers: HashSet<_> = peers.iter().collect();
+        let expired_peers: Vec<_> = self
+            .cache
+            .iter()
+            .filter(|url| !peers.contains(url))
+            .cloned()
+            .collect();
+
+        for peer in &expired_peers {
+            self.cache.remove(peer);
+        }
+
+        expired_peers
+    }
 }
 
 pub struct Importer {
@@ -57,7 +76,7 @@ pub struct Importer {
     event_receiver: mpsc::UnboundedReceiver<()>,
     event_sender: mpsc::UnboundedSender<()>,
 
-    ethereum_program: EthereumProgram,
+    ethereum_client: Box<dyn EthereumClient>,
     network_name: String,
     web3: Web3,
     bootnode_client: BootnodeClient,
@@ -80,7 +99,7 @@ impl Importer {
             event_receiver,
             event_sender,
 
-            ethereum_program,
+            ethereum_client: ethereum_client::from_program(ethereum_program),
             network_name,
             web3,
             bootnode_client,
@@ -112,17 +131,26 @@ impl Future for Importer {
                 Inner::FetchingPeers { ref mut fetcher } => match fetcher.poll() {
                     Ok(Async::NotReady) => return Ok(Async::NotReady),
                     Ok(Async::Ready(peer_urls)) => {
-                        // retrieve new peers
+                        // retrieve new peers and peers expired by the bootnode
+                        let expired_peers = self.peer_cache.expire(&peer_urls);
                         let new_peers = self.peer_cache.add(&peer_urls);
 
-                        match new_peers.len() {
-                            0 => {
+                        match (new_peers.len(), expired_peers.len()) {
+                            (0, 0) => {
                                 info!("Node Importer: No new Ethereum node fetched");
                                 Inner::idle()
                             }
-                            n => {
-                                info!("Node Importer: {} new Ethereum node(s) fetched", n);
-                                Inner::import_peers(&self.web3, self.ethereum_program, &new_peers)
+                            (n, m) => {
+                                info!(
+                                    "Node Importer: {} new Ethereum node(s) fetched, {} expired",
+                                    n, m
+                                );
+                                Inner::import_peers(
+                                    &self.web3,
+                                    self.ethereum_client.as_ref(),
+                                    &new_peers,
+                                    &expired_peers,
+                                )
                             }
                         }
                     }
@@ -134,8 +162,11 @@ impl Future for Importer {
                 },
                 Inner::ImportingPeers { ref mut importer } => match importer.poll() {
                     Ok(Async::NotReady) => return Ok(Async::NotReady),
-                    Ok(Async::Ready(n)) => {
-                        info!("Node Importer: {} Ethereum node(s) imported", n);
+                    Ok(Async::Ready((n, m))) => {
+                        info!(
+                            "Node Importer: {} Ethereum node(s) imported, {} removed",
+                            n, m
+                        );
                         Inner::idle()
                     }
                     Err(err) => {
@@ -171,37 +202,51 @@ impl Inner {
 
     fn import_peers(
         web3: &Web3,
-        ethereum_program: EthereumProgram,
+        ethereum_client: &dyn EthereumClient,
         peers: &[EthereumNodeUrl],
+        expired_peers: &[EthereumNodeUrl],
     ) -> Self {
-        let futures: Vec<_> = peers
+        let add_futures: Vec<_> = peers
             .iter()
             .map(|enode_url| {
-                let enode_url = enode_url.clone();
-                match ethereum_program {
-                    EthereumProgram::Parity => web3
-                        .parity_set()
-                        .add_reserved_peer(&enode_url.to_string())
-                        .map({
-                            let url = enode_url.clone();
-                            move |ok| {
-                                if ok {
-                                    info!("Node Importer: Add peer {:?} to Ethereum Node", url);
-                                }
-                                ok
+                ethereum_client
+                    .add_peer(web3, enode_url)
+                    .map({
+                        let url = enode_url.clone();
+                        move |ok| {
+                            if ok {
+                                info!("Node Importer: Add peer {:?} to Ethereum Node", url);
                             }
-                        })
-                        .from_err::<Error>(),
-                    EthereumProgram::GoEthereum => {
-                        unimplemented!();
-                    }
-                }
+                            ok
+                        }
+                    })
+                    .from_err::<Error>()
+            })
+            .collect();
+
+        let remove_futures: Vec<_> = expired_peers
+            .iter()
+            .map(|enode_url| {
+                ethereum_client
+                    .remove_peer(web3, enode_url)
+                    .map({
+                        let url = enode_url.clone();
+                        move |ok| {
+                            if ok {
+                                info!("Node Importer: Remove peer {:?} from Ethereum Node", url);
+                            }
+                            ok
+                        }
+                    })
+                    .from_err::<Error>()
             })
             .collect();
 
+        let count = |results: Vec<bool>| results.iter().filter(|ok| **ok).count();
         let importer = Box::new(
-            futures::future::join_all(futures)
-                .map(|results| results.iter().fold(0, |n, ok| if *ok { n + 1 } else { n }))
+            futures::future::join_all(add_futures)
+                .join(futures::future::join_all(remove_futures))
+                .map(move |(added, removed)| (count(added), count(removed)))
                 .map_err(|_| ()),
         );
 
@@ -1,6 +1,8 @@
 use futures::{sync::mpsc, Async, Future, Poll, Stream};
+use serde_json::Value as JsonValue;
 use std::path::Path;
 
+mod chainspec_watcher;
 mod error;
 mod importer;
 mod register;
@@ -8,14 +10,17 @@ mod register;
 pub use self::error::Error;
 
 use crate::bootnode::BootnodeClient;
+use crate::ethereum_client::Web3;
 use crate::primitives::EthereumProgram;
 
+use self::chainspec_watcher::ChainSpecWatcher;
 use self::importer::Importer;
 use self::register::Register;
 
-type Web3 = web3::Web3<web3::transports::Ipc>;
+pub type BlockNumberFetcher = Box<dyn Future<Item = u64, Error = Error> + Send>;
 
 enum Event {
+    CheckChainSpec,
     ImportPeers,
     RegisterEthereumNode,
     Shutdown,
@@ -23,8 +28,13 @@ enum Event {
 
 pub struct NetworkKeeper {
     web3_ipc: web3::transports::Ipc,
+    web3: Web3,
     importer: Importer,
     register: Register,
+    chainspec_watcher: Option<ChainSpecWatcher>,
+
+    network_name: String,
+    bootnode_client: BootnodeClient,
 
     event_sender: mpsc::UnboundedSender<Event>,
     event_receiver: mpsc::UnboundedReceiver<Event>,
@@ -57,12 +67,22 @@ impl NetworkKeeper {
             ws_jsonrpc_port,
         );
 
-        let importer = Importer::new(ethereum_program, network_name, web3, bootnode_client);
+        let importer = Importer::new(
+            ethereum_program,
+            network_name.clone(),
+            web3.clone(),
+            bootnode_client.clone(),
+        );
 
         NetworkKeeper {
             web3_ipc,
+            web3,
             importer,
             register,
+            chainspec_watcher: None,
+
+            network_name,
+            bootnode_client,
 
             event_sender,
             event_receiver,
@@ -79,6 +99,38 @@ impl NetworkKeeper {
         self.send_event(Event::ImportPeers);
     }
 
+    /// start watching chain specification revisions greater than `revision` on bootnode
+    pub fn watch_chainspec(&mut self, revision: u64) {
+        self.chainspec_watcher = Some(ChainSpecWatcher::new(
+            self.network_name.clone(),
+            self.bootnode_client.clone(),
+            revision,
+        ));
+    }
+
+    #[inline]
+    pub fn check_chainspec(&mut self) {
+        self.send_event(Event::CheckChainSpec);
+    }
+
+    /// greater revision of chain specification fetched from bootnode
+    pub fn take_chainspec_update(&mut self) -> Option<(u64, JsonValue)> {
+        self.chainspec_watcher
+            .as_mut()
+            .and_then(ChainSpecWatcher::take_updated_chainspec)
+    }
+
+    /// number of the latest block of Ethereum node
+    pub fn block_number(&self) -> BlockNumberFetcher {
+        Box::new(
+            self.web3
+                .eth()
+                .block_number()
+                .map(|number| number.low_u64())
+                .from_err::<Error>(),
+        )
+    }
+
     #[inline]
     #[allow(unused)]
     pub fn shutdown(&mut self) {
@@ -103,10 +155,19 @@ impl Future for NetworkKeeper {
         let _ = self.web3_ipc.poll();
         let _ = self.register.poll();
         let _ = self.importer.poll();
+        if let Some(ref mut chainspec_watcher) = self.chainspec_watcher {
+            let _ = chainspec_watcher.poll();
+        }
 
         match self.event_receiver.poll() {
             Ok(Async::Ready(Some(event))) => match event {
                 Event::Shutdown => Ok(Async::Ready(())),
+                Event::CheckChainSpec => {
+                    if let Some(ref chainspec_watcher) = self.chainspec_watcher {
+                        chainspec_watcher.check();
+                    }
+                    Ok(Async::NotReady)
+                }
                 Event::ImportPeers => {
                     self.importer.import();
                     Ok(Async::NotReady)
@@ -1,7 +1,7 @@
 use futures::{sync::mpsc, Async, Future, Poll, Stream};
-use std::str::FromStr;
 use url::Url;
 
+use crate::ethereum_client::{self, EthereumClient};
 use crate::primitives::{EthereumNodeUrl, EthereumProgram};
 
 use super::{BootnodeClient, Error, Web3};
@@ -15,7 +15,7 @@ pub struct Register {
     event_receiver: mpsc::UnboundedReceiver<()>,
     event_sender: mpsc::UnboundedSender<()>,
 
-    ethereum_program: EthereumProgram,
+    ethereum_client: Box<dyn EthereumClient>,
     network_name: String,
     web3: Web3,
     bootnode_client: BootnodeClient,
@@ -38,7 +38,7 @@ impl Register {
             inner: Inner::Idle,
             event_receiver,
             event_sender,
-            ethereum_program,
+            ethereum_client: ethereum_client::from_program(ethereum_program),
             network_name,
             web3,
             bootnode_client,
@@ -63,7 +63,7 @@ impl Future for Register {
             self.inner = match self.inner {
                 Inner::Idle => match self.event_receiver.poll() {
                     Ok(Async::Ready(Some(_))) => {
-                        Inner::fetch_url(&self.web3, self.ethereum_program)
+                        Inner::fetch_url(&self.web3, self.ethereum_client.as_ref())
                     }
                     _ => return Ok(Async::NotReady),
                 },
@@ -133,17 +133,8 @@ impl Inner {
         Inner::Idle
     }
 
-    fn fetch_url(web3: &Web3, ethereum_program: EthereumProgram) -> Self {
-        let fetcher = match ethereum_program {
-            EthereumProgram::Parity => Box::new(
-                web3.parity()
-                    .enode()
-                    .from_err::<Error>()
-                    .and_then(|enode_url| Ok(EthereumNodeUrl::from_str(&enode_url)?))
-                    .from_err::<Error>(),
-            ),
-            EthereumProgram::GoEthereum => unimplemented!(),
-        };
+    fn fetch_url(web3: &Web3, ethereum_client: &dyn EthereumClient) -> Self {
+        let fetcher = Box::new(ethereum_client.enode(web3).from_err::<Error>());
 
         Inner::FetchingUrl { fetcher }
     }
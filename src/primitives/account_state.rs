@@ -1,27 +1,40 @@
-use ethereum_types::U256;
+use ethereum_types::{H256, U256};
 use std::collections::BTreeMap;
 
-use crate::utils;
+use crate::utils::{self, rlp, trie};
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize)]
-// #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+use super::Error;
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AccountState {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "utils::deserialize_maybe_u256"
+    )]
     pub balance: Option<U256>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "utils::deserialize_maybe_u256"
+    )]
     pub nonce: Option<U256>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub constructor: Option<String>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub code: Option<String>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "utils::deserialize_maybe_u256"
+    )]
     pub version: Option<U256>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub storage: Option<BTreeMap<U256, U256>>,
 }
 
@@ -57,4 +70,77 @@ impl AccountState {
             storage,
         }
     }
+
+    /// account state in the format of `alloc` in Go-Ethereum genesis.json, None if the account
+    /// is deployed by a constructor which genesis.json does not take
+    pub fn as_geth_alloc_json(&self) -> Option<serde_json::Value> {
+        if self.constructor.is_some() {
+            return None;
+        }
+
+        let mut account = json!({
+            "balance": utils::to_0xhex(&self.balance.unwrap_or_default()),
+        });
+
+        if let Some(nonce) = self.nonce {
+            account["nonce"] = json!(utils::to_0xhex(&nonce));
+        }
+
+        if let Some(ref code) = self.code {
+            account["code"] = json!(format!("0x{}", utils::clean_0x(code)));
+        }
+
+        if let Some(ref storage) = self.storage {
+            account["storage"] = storage.iter().fold(json!({}), |mut storage, (key, value)| {
+                storage
+                    .as_object_mut()
+                    .expect("storage is an object; qed")
+                    .insert(
+                        utils::to_0xhex(&H256::from(*key)),
+                        json!(utils::to_0xhex(&H256::from(*value))),
+                    );
+                storage
+            });
+        }
+
+        Some(account)
+    }
+
+    /// RLP of account in genesis state trie: nonce, balance, storage root and code hash
+    pub fn genesis_trie_rlp(&self, address: &ethereum_types::Address) -> Result<Vec<u8>, Error> {
+        // state of constructor depends on its execution
+        if self.constructor.is_some() {
+            return Err(Error::UncomputableGenesisState(format!(
+                "constructor of {:?}",
+                address
+            )));
+        }
+
+        let code = match self.code {
+            Some(ref code) => utils::hex_to_bytes(code)
+                .ok_or_else(|| Error::InvalidAccountStateData(code.clone()))?,
+            None => vec![],
+        };
+
+        let storage_root = trie::trie_root(
+            self.storage
+                .iter()
+                .flatten()
+                .filter(|(_, value)| !value.is_zero())
+                .map(|(key, value)| {
+                    (
+                        utils::keccak256(&H256::from(*key)[..]).to_vec(),
+                        rlp::encode_u256(value),
+                    )
+                })
+                .collect(),
+        );
+
+        Ok(rlp::encode_list(&[
+            rlp::encode_u256(&self.nonce.unwrap_or_default()),
+            rlp::encode_u256(&self.balance.unwrap_or_default()),
+            rlp::encode_bytes(&storage_root[..]),
+            rlp::encode_bytes(&utils::keccak256(&code)[..]),
+        ]))
+    }
 }
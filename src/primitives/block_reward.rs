@@ -0,0 +1,74 @@
+use ethereum_types::U256;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+
+use crate::utils;
+
+/// block reward of Ethash and Aura, either constant or changing at blocks
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockReward {
+    Constant(U256),
+
+    /// rewards keyed by the block from which they are paid, no reward is paid before the first
+    Schedule(BTreeMap<u64, U256>),
+}
+
+impl BlockReward {
+    /// block reward from `blockReward` of Parity spec.json, a quantity or a map from block to
+    /// quantity
+    pub fn from_json(value: &JsonValue) -> Option<BlockReward> {
+        match value {
+            JsonValue::Object(schedule) => {
+                let schedule = schedule
+                    .iter()
+                    .map(|(block, reward)| {
+                        let block = utils::maybe_u256(block)
+                            .filter(|block| *block <= U256::from(u64::max_value()))?;
+                        Some((block.low_u64(), utils::maybe_u256_from_json_value(reward)?))
+                    })
+                    .collect::<Option<BTreeMap<_, _>>>()?;
+                if schedule.is_empty() {
+                    None
+                } else {
+                    Some(BlockReward::Schedule(schedule))
+                }
+            }
+            _ => utils::maybe_u256_from_json_value(value).map(BlockReward::Constant),
+        }
+    }
+
+    /// `blockReward` of Parity spec.json
+    pub fn as_json(&self) -> JsonValue {
+        match self {
+            BlockReward::Constant(reward) => json!(utils::to_0xhex(reward)),
+            BlockReward::Schedule(schedule) => {
+                let schedule: serde_json::Map<_, _> = schedule
+                    .iter()
+                    .map(|(block, reward)| (block.to_string(), json!(utils::to_0xhex(reward))))
+                    .collect();
+                JsonValue::Object(schedule)
+            }
+        }
+    }
+}
+
+impl Serialize for BlockReward {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_json().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockReward {
+    fn deserialize<D>(deserializer: D) -> Result<BlockReward, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = JsonValue::deserialize(deserializer)?;
+        BlockReward::from_json(&value)
+            .ok_or_else(|| de::Error::custom(format!("invalid block reward: {}", value)))
+    }
+}
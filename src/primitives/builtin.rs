@@ -0,0 +1,167 @@
+use ethereum_types::Address;
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::utils;
+
+use super::{AccountState, Error, ForkSchedule, HardFork};
+
+/// addresses of precompiled contracts are 0x01 to BUILTIN_COUNT
+const BUILTIN_COUNT: u8 = 9;
+
+fn builtin_address(index: u8) -> Address {
+    let mut address = [0u8; 20];
+    address[19] = index;
+    Address::from(address)
+}
+
+fn is_builtin_address(address: &Address) -> bool {
+    (1..=BUILTIN_COUNT).any(|index| builtin_address(index) == *address)
+}
+
+/// account states at builtin addresses may only hold balance and nonce, code and storage would
+/// be shadowed by precompiled contracts
+pub fn check_builtin_conflicts<'a, I>(account_states: I) -> Result<(), Error>
+where
+    I: IntoIterator<Item = (&'a Address, &'a AccountState)>,
+{
+    for (address, state) in account_states {
+        if is_builtin_address(address)
+            && (state.code.is_some() || state.constructor.is_some() || state.storage.is_some())
+        {
+            return Err(Error::ConflictingBuiltinAccount(*address));
+        }
+    }
+    Ok(())
+}
+
+/// builtin accounts of Parity spec.json keyed by address, pricing follows the fork schedule
+/// and precompiles of unscheduled hard forks are omitted
+///
+/// a builtin with a single price is written in the legacy format with `activate_at`, which all
+/// versions of Parity Ethereum read, prices keyed by block need OpenEthereum 3.0 or later
+pub fn parity_builtin_accounts(fork_schedule: &ForkSchedule) -> serde_json::Map<String, JsonValue> {
+    let byzantium = fork_schedule.hard_fork_block(HardFork::Byzantium);
+    let istanbul = fork_schedule.hard_fork_block(HardFork::Istanbul);
+    let berlin = fork_schedule.hard_fork_block(HardFork::Berlin);
+
+    let builtins = vec![
+        (
+            "ecrecover",
+            vec![(Some(0), json!({ "linear": { "base": 3000, "word": 0 } }))],
+        ),
+        (
+            "sha256",
+            vec![(Some(0), json!({ "linear": { "base": 60, "word": 12 } }))],
+        ),
+        (
+            "ripemd160",
+            vec![(Some(0), json!({ "linear": { "base": 600, "word": 120 } }))],
+        ),
+        (
+            "identity",
+            vec![(Some(0), json!({ "linear": { "base": 15, "word": 3 } }))],
+        ),
+        (
+            "modexp",
+            vec![
+                (byzantium, json!({ "modexp": { "divisor": 20 } })),
+                // EIP-2565
+                (berlin, json!({ "modexp2565": {} })),
+            ],
+        ),
+        (
+            "alt_bn128_add",
+            vec![
+                (
+                    byzantium,
+                    json!({ "alt_bn128_const_operations": { "price": 500 } }),
+                ),
+                // EIP-1108
+                (
+                    istanbul,
+                    json!({ "alt_bn128_const_operations": { "price": 150 } }),
+                ),
+            ],
+        ),
+        (
+            "alt_bn128_mul",
+            vec![
+                (
+                    byzantium,
+                    json!({ "alt_bn128_const_operations": { "price": 40000 } }),
+                ),
+                (
+                    istanbul,
+                    json!({ "alt_bn128_const_operations": { "price": 6000 } }),
+                ),
+            ],
+        ),
+        (
+            "alt_bn128_pairing",
+            vec![
+                (
+                    byzantium,
+                    json!({ "alt_bn128_pairing": { "base": 100000, "pair": 80000 } }),
+                ),
+                (
+                    istanbul,
+                    json!({ "alt_bn128_pairing": { "base": 45000, "pair": 34000 } }),
+                ),
+            ],
+        ),
+        (
+            "blake2_f",
+            vec![(istanbul, json!({ "blake2_f": { "gas_per_round": 1 } }))],
+        ),
+    ];
+
+    let mut accounts = serde_json::Map::new();
+    for (index, (name, prices)) in builtins.into_iter().enumerate() {
+        // later prices of the same block take precedence
+        let pricing: BTreeMap<u64, JsonValue> = prices
+            .into_iter()
+            .filter_map(|(block, price)| block.map(|block| (block, price)))
+            .collect();
+        if pricing.is_empty() {
+            continue;
+        }
+
+        let builtin = if pricing.len() == 1 {
+            let (block, price) = pricing
+                .into_iter()
+                .next()
+                .expect("pricing has one entry; qed");
+            let mut builtin = json!({ "name": name, "pricing": price });
+            if block > 0 {
+                builtin["activate_at"] = json!(block);
+            }
+            builtin
+        } else {
+            let pricing: serde_json::Map<String, JsonValue> = pricing
+                .into_iter()
+                .map(|(block, price)| (block.to_string(), json!({ "price": price })))
+                .collect();
+            json!({ "name": name, "pricing": pricing })
+        };
+
+        accounts.insert(
+            utils::to_0xhex(&builtin_address(index as u8 + 1)),
+            json!({
+                "balance": "1",
+                "builtin": builtin
+            }),
+        );
+    }
+
+    accounts
+}
+
+/// addresses of builtin accounts in Parity spec.json
+pub fn parity_builtin_addresses(fork_schedule: &ForkSchedule) -> Vec<Address> {
+    parity_builtin_accounts(fork_schedule)
+        .keys()
+        .filter_map(|address| Address::from_str(utils::clean_0x(address)).ok())
+        .collect()
+}
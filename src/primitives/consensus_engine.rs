@@ -1,58 +1,221 @@
 use ethereum_types::{Address, U256};
+use serde_json::Value as JsonValue;
+use std::str::FromStr;
 
-use crate::primitives::EthereumProgram;
+use crate::primitives::{BlockReward, Error, EthereumProgram, ValidatorSet};
+use crate::utils;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ConsensusEngine {
     Ethash {
         genesis_difficulty: U256,
+        minimum_difficulty: U256,
+        difficulty_bound_divisor: U256,
+        duration_limit: u64,
+        block_reward: BlockReward,
+        homestead_transition: u64,
+        bomb_defuse_transition: u64,
     },
     ParityAura {
         block_period: u64,
-        block_reward: U256,
-        validators: Vec<Address>,
+        block_reward: BlockReward,
+        validators: ValidatorSet,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        maximum_uncle_count: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        empty_steps_transition: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        maximum_empty_steps: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block_reward_contract_address: Option<Address>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block_reward_contract_transition: Option<u64>,
     },
     ParityTendermint {
         propose_timeout: u64,
         prevote_timeout: u64,
         precommit_timeout: u64,
         commit_timeout: u64,
+        block_reward: BlockReward,
+        validators: ValidatorSet,
+    },
+    GethClique {
+        block_period: u64,
+        epoch: u64,
         block_reward: U256,
         validators: Vec<Address>,
     },
-    GethClique {
+    BesuIbft2 {
         block_period: u64,
+        epoch: u64,
+        request_timeout: u64,
         block_reward: U256,
         validators: Vec<Address>,
     },
 }
 
 impl ConsensusEngine {
+    /// consensus engine from `engine`, `genesis` and `accounts` sections of Parity spec.json
+    pub fn from_parity_spec(
+        engine: &JsonValue,
+        genesis: &JsonValue,
+        accounts: &JsonValue,
+    ) -> Result<ConsensusEngine, Error> {
+        let invalid = |reason: &str| Error::InvalidParityChainSpec(reason.to_owned());
+
+        if let Some(params) = engine
+            .get("Ethash")
+            .or_else(|| engine.get("ethash"))
+            .map(|ethash| &ethash["params"])
+        {
+            return Ok(ConsensusEngine::Ethash {
+                genesis_difficulty: utils::maybe_u256_from_json_value(&genesis["difficulty"])
+                    .ok_or_else(|| invalid("genesis.difficulty"))?,
+                minimum_difficulty: utils::maybe_u256_from_json_value(&params["minimumDifficulty"])
+                    .ok_or_else(|| invalid("minimumDifficulty"))?,
+                difficulty_bound_divisor: utils::maybe_u256_from_json_value(
+                    &params["difficultyBoundDivisor"],
+                )
+                .ok_or_else(|| invalid("difficultyBoundDivisor"))?,
+                duration_limit: utils::maybe_u64_from_json_value(&params["durationLimit"])
+                    .ok_or_else(|| invalid("durationLimit"))?,
+                block_reward: parity_block_reward(&params["blockReward"])
+                    .ok_or_else(|| invalid("blockReward"))?,
+                homestead_transition: utils::maybe_u64_from_json_value(
+                    &params["homesteadTransition"],
+                )
+                .unwrap_or(0),
+                bomb_defuse_transition: utils::maybe_u64_from_json_value(
+                    &params["bombDefuseTransition"],
+                )
+                .unwrap_or(0),
+            });
+        }
+
+        if let Some(params) = engine.get("authorityRound").map(|aura| &aura["params"]) {
+            return Ok(ConsensusEngine::ParityAura {
+                block_period: utils::maybe_u64_from_json_value(&params["stepDuration"])
+                    .ok_or_else(|| invalid("stepDuration"))?,
+                block_reward: parity_block_reward(&params["blockReward"])
+                    .ok_or_else(|| invalid("blockReward"))?,
+                validators: ValidatorSet::from_parity_spec(&params["validators"], accounts)?,
+                maximum_uncle_count: utils::maybe_u64_from_json_value(&params["maximumUncleCount"]),
+                empty_steps_transition: utils::maybe_u64_from_json_value(
+                    &params["emptyStepsTransition"],
+                ),
+                maximum_empty_steps: utils::maybe_u64_from_json_value(&params["maximumEmptySteps"]),
+                block_reward_contract_address: match params["blockRewardContractAddress"] {
+                    JsonValue::Null => None,
+                    ref address => Some(
+                        address
+                            .as_str()
+                            .and_then(|address| Address::from_str(utils::clean_0x(address)).ok())
+                            .ok_or_else(|| invalid("blockRewardContractAddress"))?,
+                    ),
+                },
+                block_reward_contract_transition: utils::maybe_u64_from_json_value(
+                    &params["blockRewardContractTransition"],
+                ),
+            });
+        }
+
+        if let Some(params) = engine
+            .get("tendermint")
+            .map(|tendermint| &tendermint["params"])
+        {
+            return Ok(ConsensusEngine::ParityTendermint {
+                propose_timeout: utils::maybe_u64_from_json_value(&params["timeoutPropose"])
+                    .unwrap_or(10000),
+                prevote_timeout: utils::maybe_u64_from_json_value(&params["timeoutPrevote"])
+                    .unwrap_or(10000),
+                precommit_timeout: utils::maybe_u64_from_json_value(&params["timeoutPrecommit"])
+                    .unwrap_or(10000),
+                commit_timeout: utils::maybe_u64_from_json_value(&params["timeoutCommit"])
+                    .unwrap_or(10000),
+                block_reward: parity_block_reward(&params["blockReward"])
+                    .ok_or_else(|| invalid("blockReward"))?,
+                validators: ValidatorSet::from_parity_spec(&params["validators"], accounts)?,
+            });
+        }
+
+        Err(Error::InvalidConsensusEngineType(engine.to_string()))
+    }
+
+    /// default Ethereum program of consensus engine
     pub fn program(&self) -> EthereumProgram {
         match self {
             ConsensusEngine::Ethash { .. } => EthereumProgram::Parity,
             ConsensusEngine::ParityAura { .. } => EthereumProgram::Parity,
             ConsensusEngine::ParityTendermint { .. } => EthereumProgram::Parity,
             ConsensusEngine::GethClique { .. } => EthereumProgram::GoEthereum,
+            ConsensusEngine::BesuIbft2 { .. } => EthereumProgram::Besu,
         }
     }
 
+    /// name of consensus engine in network config
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConsensusEngine::Ethash { .. } => "ethash",
+            ConsensusEngine::ParityAura { .. } => "aura",
+            ConsensusEngine::ParityTendermint { .. } => "tendermint",
+            ConsensusEngine::GethClique { .. } => "clique",
+            ConsensusEngine::BesuIbft2 { .. } => "ibft2",
+        }
+    }
+
+    /// all validators of consensus engine, including those of later transitions
     pub fn validators(&self) -> Option<Vec<Address>> {
         match self {
             ConsensusEngine::Ethash { .. } => None,
-            ConsensusEngine::ParityAura { validators, .. } => Some(validators.clone()),
-            ConsensusEngine::ParityTendermint { validators, .. } => Some(validators.clone()),
+            ConsensusEngine::ParityAura { validators, .. } => Some(validators.validators()),
+            ConsensusEngine::ParityTendermint { validators, .. } => Some(validators.validators()),
             ConsensusEngine::GethClique { validators, .. } => Some(validators.clone()),
+            ConsensusEngine::BesuIbft2 { validators, .. } => Some(validators.clone()),
         }
     }
 
-    pub fn validator_count(&self) -> usize {
+    /// validators of genesis block
+    pub fn genesis_validators(&self) -> Option<Vec<Address>> {
         match self {
-            ConsensusEngine::Ethash { .. } => 0,
-            ConsensusEngine::ParityAura { validators, .. } => validators.len(),
-            ConsensusEngine::ParityTendermint { validators, .. } => validators.len(),
-            ConsensusEngine::GethClique { validators, .. } => validators.len(),
+            ConsensusEngine::ParityAura { validators, .. }
+            | ConsensusEngine::ParityTendermint { validators, .. } => {
+                Some(validators.validators_at(0))
+            }
+            _ => self.validators(),
         }
     }
+
+    /// block from which `validator` is allowed to seal blocks, None if it never is
+    pub fn validator_activation_block(&self, validator: &Address) -> Option<u64> {
+        match self {
+            ConsensusEngine::Ethash { .. } => Some(0),
+            ConsensusEngine::ParityAura { validators, .. }
+            | ConsensusEngine::ParityTendermint { validators, .. } => {
+                validators.activation_block(validator)
+            }
+            ConsensusEngine::GethClique { validators, .. }
+            | ConsensusEngine::BesuIbft2 { validators, .. } => {
+                if validators.contains(validator) {
+                    Some(0)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    pub fn validator_count(&self) -> usize {
+        self.validators()
+            .map(|validators| validators.len())
+            .unwrap_or(0)
+    }
+}
+
+/// block reward of Parity spec.json, which is zero if absent
+fn parity_block_reward(value: &JsonValue) -> Option<BlockReward> {
+    match value {
+        JsonValue::Null => Some(BlockReward::Constant(U256::zero())),
+        _ => BlockReward::from_json(value),
+    }
 }
@@ -0,0 +1,434 @@
+use ethereum_types::{H256, U256};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::utils::{
+    self,
+    abi::{self, ParamType},
+};
+
+use super::network_config::Quantity;
+use super::{AccountState, Error, EthereumProgram};
+
+/// contract placed in genesis state from a compiled artifact
+///
+/// ```toml
+/// [genesisContracts."0x0000000000000000000000000000000000002000"]
+/// artifact = "Token"
+/// balance = "0"
+///
+/// [genesisContracts."0x0000000000000000000000000000000000002000".storage]
+/// totalSupply = "1000000"
+/// "balances[0x0053f97dc01ce07602b208f844b35e8484acf69f]" = "1000000"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GenesisContract {
+    /// contract name, or path of artifact file relative to artifacts directory
+    pub artifact: String,
+
+    /// the contract is deployed by its constructor with these arguments instead of placing
+    /// deployed bytecode
+    #[serde(default)]
+    pub constructor_arguments: Option<Vec<JsonValue>>,
+
+    pub balance: Option<Quantity>,
+    pub nonce: Option<Quantity>,
+
+    /// initial values of state variables, entries of mappings are keyed like `balances[0x...]`
+    #[serde(default)]
+    pub storage: BTreeMap<String, JsonValue>,
+}
+
+impl GenesisContract {
+    /// only Parity Ethereum runs constructors of genesis accounts
+    pub fn validate(&self, program: EthereumProgram) -> Result<(), Error> {
+        match (program, &self.constructor_arguments) {
+            (EthereumProgram::Parity, _) | (_, None) => Ok(()),
+            (_, Some(_)) => Err(Error::InvalidConstructorArguments(format!(
+                "constructor of {} is not run by {:?}",
+                self.artifact, program
+            ))),
+        }
+    }
+
+    /// account state of contract, balance, nonce and storage of `state` are kept unless they
+    /// are overridden
+    pub fn account_state(
+        &self,
+        artifacts_dir: &Path,
+        mut state: AccountState,
+    ) -> Result<AccountState, Error> {
+        let artifact = ContractArtifact::find(artifacts_dir, &self.artifact)?;
+
+        match self.constructor_arguments {
+            Some(ref arguments) => {
+                state.constructor = Some(artifact.constructor_code(arguments)?);
+                state.code = None;
+            }
+            None => {
+                state.code = Some(artifact.deployed_bytecode.clone());
+                state.constructor = None;
+            }
+        }
+
+        if let Some(balance) = self.balance {
+            state.balance = Some(balance.0);
+        }
+        if let Some(nonce) = self.nonce {
+            state.nonce = Some(nonce.0);
+        }
+        if !self.storage.is_empty() {
+            state
+                .storage
+                .get_or_insert_with(BTreeMap::default)
+                .extend(artifact.storage(&self.storage)?);
+        }
+
+        Ok(state)
+    }
+}
+
+/// compiled contract read from a Truffle, Hardhat or solc JSON artifact
+#[derive(Debug, Clone)]
+pub struct ContractArtifact {
+    pub name: String,
+    pub abi: JsonValue,
+    pub bytecode: String,
+    pub deployed_bytecode: String,
+    pub storage_layout: Option<JsonValue>,
+}
+
+impl ContractArtifact {
+    /// find artifact of `name` in `artifacts_dir`, either by path, by file name or in the
+    /// output of solc
+    pub fn find(artifacts_dir: &Path, name: &str) -> Result<ContractArtifact, Error> {
+        if name.ends_with(".json") {
+            let content = std::fs::read_to_string(artifacts_dir.join(name))?;
+            let artifact: JsonValue = serde_json::from_str(&content)?;
+            let name = artifact["contractName"]
+                .as_str()
+                .or_else(|| {
+                    Path::new(name)
+                        .file_stem()
+                        .and_then(std::ffi::OsStr::to_str)
+                })
+                .unwrap_or(name)
+                .to_owned();
+            return Self::from_json(name, &artifact);
+        }
+
+        let mut files = Vec::new();
+        json_files(artifacts_dir, &mut files)?;
+        files.sort();
+
+        let mut named_files = files
+            .iter()
+            .filter(|file| file.file_stem().and_then(std::ffi::OsStr::to_str) == Some(name));
+        if let Some(file) = named_files.next() {
+            if named_files.next().is_some() {
+                return Err(Error::InvalidContractArtifact(format!(
+                    "multiple artifacts of {} in {:?}",
+                    name, artifacts_dir
+                )));
+            }
+            let artifact: JsonValue = serde_json::from_str(&std::fs::read_to_string(file)?)?;
+            return Self::from_json(name.to_owned(), &artifact);
+        }
+
+        // standard JSON output or combined JSON output of solc
+        for file in files.iter() {
+            let output: JsonValue = serde_json::from_str(&std::fs::read_to_string(file)?)?;
+            let contracts = match output["contracts"].as_object() {
+                Some(contracts) => contracts,
+                None => continue,
+            };
+            for (key, contract) in contracts {
+                if key.rsplit(':').next() == Some(name) {
+                    return Self::from_json(name.to_owned(), contract);
+                }
+                if let Some(contract) = contract.get(name) {
+                    return Self::from_json(name.to_owned(), contract);
+                }
+            }
+        }
+
+        Err(Error::ContractArtifactNotFound(name.to_owned()))
+    }
+
+    pub fn from_json(name: String, artifact: &JsonValue) -> Result<ContractArtifact, Error> {
+        let invalid = |field: &str| {
+            Error::InvalidContractArtifact(format!("{} of {} is absent or invalid", field, name))
+        };
+
+        let bytecode = bytecode_of(&artifact["bytecode"])
+            .or_else(|| bytecode_of(&artifact["evm"]["bytecode"]))
+            .or_else(|| bytecode_of(&artifact["bin"]))
+            .ok_or_else(|| invalid("bytecode"))?;
+        let deployed_bytecode = bytecode_of(&artifact["deployedBytecode"])
+            .or_else(|| bytecode_of(&artifact["evm"]["deployedBytecode"]))
+            .or_else(|| bytecode_of(&artifact["bin-runtime"]))
+            .ok_or_else(|| invalid("deployedBytecode"))?;
+
+        // ABI is a string in combined JSON output of solc
+        let abi = match artifact["abi"] {
+            JsonValue::String(ref abi) => serde_json::from_str(abi)?,
+            ref abi => abi.clone(),
+        };
+
+        let storage_layout = match artifact["storageLayout"] {
+            JsonValue::Null => None,
+            JsonValue::String(ref layout) => Some(serde_json::from_str(layout)?),
+            ref layout => Some(layout.clone()),
+        };
+
+        Ok(ContractArtifact {
+            name,
+            abi,
+            bytecode,
+            deployed_bytecode,
+            storage_layout,
+        })
+    }
+
+    /// creation code followed by ABI encoded constructor arguments
+    pub fn constructor_code(&self, arguments: &[JsonValue]) -> Result<String, Error> {
+        let invalid = || {
+            Error::InvalidConstructorArguments(format!(
+                "{}({})",
+                self.name,
+                JsonValue::from(arguments.to_vec())
+            ))
+        };
+
+        let inputs = self
+            .abi
+            .as_array()
+            .and_then(|abi| abi.iter().find(|item| item["type"] == "constructor"))
+            .and_then(|constructor| constructor["inputs"].as_array().cloned())
+            .unwrap_or_default();
+        let types = inputs
+            .iter()
+            .map(|input| input["type"].as_str().and_then(ParamType::parse))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+
+        let encoded = abi::encode(&types, arguments).ok_or_else(invalid)?;
+        Ok(format!(
+            "{}{}",
+            self.bytecode,
+            &utils::bytes_to_0xhex(&encoded)[2..]
+        ))
+    }
+
+    /// storage slots of state variables from the storage layout of artifact
+    pub fn storage(
+        &self,
+        variables: &BTreeMap<String, JsonValue>,
+    ) -> Result<BTreeMap<U256, U256>, Error> {
+        let layout = self.storage_layout.as_ref().ok_or_else(|| {
+            Error::InvalidContractArtifact(format!("storageLayout of {} is absent", self.name))
+        })?;
+
+        let mut storage = BTreeMap::default();
+        for (variable, value) in variables {
+            let invalid = || Error::InvalidStorageVariable(format!("{} = {}", variable, value));
+            let (slot, offset, kind) = variable_location(layout, variable).ok_or_else(invalid)?;
+            store_value(layout, &mut storage, slot, offset, &kind, value).ok_or_else(invalid)?;
+        }
+        Ok(storage)
+    }
+}
+
+fn json_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            json_files(&path, files)?;
+        } else if path.extension().and_then(std::ffi::OsStr::to_str) == Some("json") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 0x-prefixed bytecode, which must be non-empty and linked
+fn bytecode_of(value: &JsonValue) -> Option<String> {
+    let bytecode = match value {
+        JsonValue::String(bytecode) => bytecode.as_str(),
+        JsonValue::Object(_) => value["object"].as_str()?,
+        _ => return None,
+    };
+    match utils::hex_to_bytes(bytecode) {
+        Some(ref bytes) if !bytes.is_empty() => Some(format!("0x{}", utils::clean_0x(bytecode))),
+        _ => None,
+    }
+}
+
+/// slot, byte offset in slot and type of a state variable or an entry of mapping
+fn variable_location(layout: &JsonValue, variable: &str) -> Option<(U256, usize, String)> {
+    let (label, mut keys) = match variable.find('[') {
+        Some(index) => (&variable[..index], &variable[index..]),
+        None => (variable, ""),
+    };
+
+    let entry = layout["storage"]
+        .as_array()?
+        .iter()
+        .find(|entry| entry["label"] == label)?;
+    let mut slot = utils::maybe_u256_from_json_value(&entry["slot"])?;
+    let mut offset = entry["offset"].as_u64()? as usize;
+    let mut kind = entry["type"].as_str()?.to_owned();
+
+    while !keys.is_empty() {
+        if !keys.starts_with('[') {
+            return None;
+        }
+        let end = keys.find(']')?;
+        let key = keys[1..end].trim();
+        keys = &keys[end + 1..];
+
+        let mapping = &layout["types"][&kind];
+        if mapping["encoding"] != "mapping" {
+            return None;
+        }
+        let key_type = &layout["types"][mapping["key"].as_str()?];
+        let mut data = match key_type["encoding"].as_str()? {
+            // keys of string and bytes are hashed without padding
+            "bytes" if key_type["label"] == "string" => key.as_bytes().to_vec(),
+            "bytes" => utils::hex_to_bytes(key)?,
+            _ => {
+                let key = match key {
+                    "true" => JsonValue::Bool(true),
+                    "false" => JsonValue::Bool(false),
+                    _ => JsonValue::String(key.to_owned()),
+                };
+                abi::encode_word(&value_param_type(key_type)?, &key)?.to_vec()
+            }
+        };
+        data.extend_from_slice(&H256::from(slot)[..]);
+
+        slot = U256::from(&utils::keccak256(&data)[..]);
+        offset = 0;
+        kind = mapping["value"].as_str()?.to_owned();
+    }
+
+    Some((slot, offset, kind))
+}
+
+/// ABI type of a value type in storage layout, contracts and enums are stored as address and
+/// unsigned integer
+fn value_param_type(kind: &JsonValue) -> Option<ParamType> {
+    let label = kind["label"].as_str()?;
+    if label.starts_with("contract ") || label == "address payable" {
+        Some(ParamType::Address)
+    } else if label.starts_with("enum ") {
+        let size = utils::maybe_u256_from_json_value(&kind["numberOfBytes"])?.low_u64();
+        Some(ParamType::Uint(size as usize * 8))
+    } else {
+        ParamType::parse(label)
+    }
+}
+
+fn store_value(
+    layout: &JsonValue,
+    storage: &mut BTreeMap<U256, U256>,
+    slot: U256,
+    offset: usize,
+    kind: &str,
+    value: &JsonValue,
+) -> Option<()> {
+    let kind = &layout["types"][kind];
+    match kind["encoding"].as_str()? {
+        "inplace" => {
+            let size = utils::maybe_u256_from_json_value(&kind["numberOfBytes"])?.low_u64();
+            store_word(storage, slot, offset, size as usize, kind, value)
+        }
+        "bytes" => {
+            let data = match kind["label"].as_str()? {
+                "string" => value.as_str()?.as_bytes().to_vec(),
+                _ => utils::hex_to_bytes(value.as_str()?)?,
+            };
+            // short data is stored with its length in the lowest byte, long data is stored
+            // from the keccak256 hash of slot
+            if data.len() < 32 {
+                let mut word = [0u8; 32];
+                word[..data.len()].copy_from_slice(&data);
+                word[31] = data.len() as u8 * 2;
+                storage.insert(slot, U256::from(&word[..]));
+            } else {
+                storage.insert(slot, U256::from(data.len() * 2 + 1));
+                let data_slot = U256::from(&utils::keccak256(&H256::from(slot)[..])[..]);
+                for (index, chunk) in data.chunks(32).enumerate() {
+                    let mut word = [0u8; 32];
+                    word[..chunk.len()].copy_from_slice(chunk);
+                    storage.insert(data_slot + U256::from(index), U256::from(&word[..]));
+                }
+            }
+            Some(())
+        }
+        "dynamic_array" => {
+            let items = value.as_array()?;
+            let item_kind = &layout["types"][kind["base"].as_str()?];
+            if item_kind["encoding"] != "inplace" {
+                return None;
+            }
+            let size =
+                utils::maybe_u256_from_json_value(&item_kind["numberOfBytes"])?.low_u64() as usize;
+            if size == 0 || size > 32 {
+                return None;
+            }
+
+            // items smaller than 16 bytes are packed into slots
+            let items_per_slot = 32 / size;
+            storage.insert(slot, U256::from(items.len()));
+            let data_slot = U256::from(&utils::keccak256(&H256::from(slot)[..])[..]);
+            for (index, item) in items.iter().enumerate() {
+                store_word(
+                    storage,
+                    data_slot + U256::from(index / items_per_slot),
+                    index % items_per_slot * size,
+                    size,
+                    item_kind,
+                    item,
+                )?;
+            }
+            Some(())
+        }
+        _ => None,
+    }
+}
+
+/// store value type of `size` bytes at `offset` in slot, other values packed into the slot
+/// are kept
+fn store_word(
+    storage: &mut BTreeMap<U256, U256>,
+    slot: U256,
+    offset: usize,
+    size: usize,
+    kind: &JsonValue,
+    value: &JsonValue,
+) -> Option<()> {
+    if offset + size > 32 {
+        return None;
+    }
+
+    let param_type = value_param_type(kind)?;
+    let mut word = U256::from(&abi::encode_word(&param_type, value)?[..]);
+    match param_type {
+        // fixed bytes are right aligned in storage
+        ParamType::FixedBytes(size) => word = word >> ((32 - size) * 8),
+        ParamType::Uint(_) | ParamType::Int(_) | ParamType::Address | ParamType::Bool => {}
+        _ => return None,
+    }
+
+    let mask = if size == 32 {
+        U256::max_value()
+    } else {
+        (U256::one() << (size * 8)) - 1
+    };
+    let stored = storage.entry(slot).or_insert_with(U256::zero);
+    *stored = (*stored & !(mask << (offset * 8))) | ((word & mask) << (offset * 8));
+    Some(())
+}
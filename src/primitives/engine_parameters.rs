@@ -0,0 +1,238 @@
+use ethereum_types::{Address, U256};
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+
+use super::network_config::Quantity;
+use super::{BlockReward, Error};
+
+lazy_static! {
+    static ref DEFAULT_BLOCK_REWARD: U256 = U256::from(5) * U256::from(10).pow(18.into());
+}
+
+const DEFAULT_CLIQUE_EPOCH: u64 = 30000;
+const DEFAULT_IBFT2_EPOCH: u64 = 30000;
+
+/// parameters of Ethash, the `ethash` entry of consensus config
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct EthashParameters {
+    pub genesis_block_difficulty: Quantity,
+    pub minimum_difficulty: Quantity,
+    pub difficulty_bound_divisor: Quantity,
+    pub duration_limit: u64,
+    pub block_reward: BlockReward,
+    pub homestead_transition: u64,
+    pub bomb_defuse_transition: u64,
+}
+
+impl Default for EthashParameters {
+    fn default() -> EthashParameters {
+        EthashParameters {
+            genesis_block_difficulty: Quantity(U256::from(16384)),
+            minimum_difficulty: Quantity(U256::from(0x20000)),
+            difficulty_bound_divisor: Quantity(U256::from(0x800)),
+            duration_limit: 13,
+            block_reward: BlockReward::Constant(*DEFAULT_BLOCK_REWARD),
+            homestead_transition: 0,
+            bomb_defuse_transition: 0,
+        }
+    }
+}
+
+/// parameters of Aura of Parity Ethereum
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct AuraParameters {
+    /// step duration in seconds
+    pub block_period: u64,
+
+    /// constant reward, or rewards keyed by the block from which they are paid
+    pub block_reward: BlockReward,
+
+    /// validator set entry, see `ValidatorSet::from_engine_parameter`
+    pub validators: JsonValue,
+
+    pub maximum_uncle_count: Option<u64>,
+    pub empty_steps_transition: Option<u64>,
+    pub maximum_empty_steps: Option<u64>,
+
+    /// contract which computes block rewards from the transition block on
+    pub block_reward_contract_address: Option<Address>,
+    pub block_reward_contract_transition: Option<u64>,
+
+    /// code of block reward contract, which is deployed to the contract address in genesis
+    pub block_reward_contract_code: Option<String>,
+}
+
+impl Default for AuraParameters {
+    fn default() -> AuraParameters {
+        AuraParameters {
+            block_period: 7,
+            block_reward: BlockReward::Constant(*DEFAULT_BLOCK_REWARD),
+            validators: JsonValue::Null,
+            maximum_uncle_count: None,
+            empty_steps_transition: None,
+            maximum_empty_steps: None,
+            block_reward_contract_address: None,
+            block_reward_contract_transition: None,
+            block_reward_contract_code: None,
+        }
+    }
+}
+
+/// parameters of Tendermint of Parity Ethereum, timeouts are in milliseconds
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct TendermintParameters {
+    pub propose_timeout: u64,
+    pub prevote_timeout: u64,
+    pub precommit_timeout: u64,
+    pub commit_timeout: u64,
+
+    /// constant reward, Parity does not take a schedule for Tendermint
+    pub block_reward: BlockReward,
+
+    /// validator set entry, see `ValidatorSet::from_engine_parameter`
+    pub validators: JsonValue,
+}
+
+impl Default for TendermintParameters {
+    fn default() -> TendermintParameters {
+        TendermintParameters {
+            propose_timeout: 10000,
+            prevote_timeout: 10000,
+            precommit_timeout: 10000,
+            commit_timeout: 10000,
+            block_reward: BlockReward::Constant(*DEFAULT_BLOCK_REWARD),
+            validators: JsonValue::Null,
+        }
+    }
+}
+
+/// parameters of Clique
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct CliqueParameters {
+    pub block_period: u64,
+    pub epoch: u64,
+    pub block_reward: Quantity,
+}
+
+impl Default for CliqueParameters {
+    fn default() -> CliqueParameters {
+        CliqueParameters {
+            block_period: 7,
+            epoch: DEFAULT_CLIQUE_EPOCH,
+            block_reward: Quantity(*DEFAULT_BLOCK_REWARD),
+        }
+    }
+}
+
+/// parameters of IBFT 2.0 of Hyperledger Besu, request timeout is in seconds
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct Ibft2Parameters {
+    pub block_period: u64,
+    pub epoch: u64,
+    pub request_timeout: u64,
+    pub block_reward: Quantity,
+}
+
+impl Default for Ibft2Parameters {
+    fn default() -> Ibft2Parameters {
+        Ibft2Parameters {
+            block_period: 5,
+            epoch: DEFAULT_IBFT2_EPOCH,
+            request_timeout: 10,
+            block_reward: Quantity(*DEFAULT_BLOCK_REWARD),
+        }
+    }
+}
+
+/// engine parameters whose values are range checked after parsing
+pub trait EngineParameters: DeserializeOwned {
+    /// positive integer parameters keyed by name
+    fn positive_values(&self) -> Vec<(&'static str, u64)>;
+
+    /// parse engine parameters from `source`, which names the environment variable or the
+    /// config entry in errors
+    fn parse(source: &str, parameters: &JsonValue) -> Result<Self, Error> {
+        let invalid = |key: &str, reason: String| Error::InvalidEngineParameter {
+            source: source.to_owned(),
+            key: key.to_owned(),
+            reason,
+        };
+
+        let entries = match parameters {
+            JsonValue::Null => serde_json::Map::new(),
+            JsonValue::Object(entries) => entries.clone(),
+            _ => {
+                return Err(invalid(
+                    "",
+                    format!("expected an object, found {}", parameters),
+                ))
+            }
+        };
+
+        // errors of serde do not tell the key of a wrong value, so that each entry is parsed
+        // on its own
+        for (key, value) in entries.iter() {
+            let mut entry = serde_json::Map::new();
+            entry.insert(key.clone(), value.clone());
+            serde_json::from_value::<Self>(JsonValue::Object(entry))
+                .map_err(|err| invalid(key, err.to_string()))?;
+        }
+
+        let parameters = serde_json::from_value::<Self>(JsonValue::Object(entries))
+            .map_err(|err| invalid("", err.to_string()))?;
+        for (key, value) in parameters.positive_values() {
+            if value == 0 {
+                return Err(invalid(key, "must be greater than 0".to_owned()));
+            }
+        }
+        Ok(parameters)
+    }
+}
+
+impl EngineParameters for EthashParameters {
+    fn positive_values(&self) -> Vec<(&'static str, u64)> {
+        vec![("durationLimit", self.duration_limit)]
+    }
+}
+
+impl EngineParameters for AuraParameters {
+    fn positive_values(&self) -> Vec<(&'static str, u64)> {
+        let mut values = vec![("blockPeriod", self.block_period)];
+        if let Some(maximum_empty_steps) = self.maximum_empty_steps {
+            values.push(("maximumEmptySteps", maximum_empty_steps));
+        }
+        values
+    }
+}
+
+impl EngineParameters for TendermintParameters {
+    fn positive_values(&self) -> Vec<(&'static str, u64)> {
+        vec![
+            ("proposeTimeout", self.propose_timeout),
+            ("prevoteTimeout", self.prevote_timeout),
+            ("precommitTimeout", self.precommit_timeout),
+            ("commitTimeout", self.commit_timeout),
+        ]
+    }
+}
+
+impl EngineParameters for CliqueParameters {
+    fn positive_values(&self) -> Vec<(&'static str, u64)> {
+        vec![("blockPeriod", self.block_period), ("epoch", self.epoch)]
+    }
+}
+
+impl EngineParameters for Ibft2Parameters {
+    fn positive_values(&self) -> Vec<(&'static str, u64)> {
+        vec![
+            ("blockPeriod", self.block_period),
+            ("epoch", self.epoch),
+            ("requestTimeout", self.request_timeout),
+        ]
+    }
+}
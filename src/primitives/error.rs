@@ -1,3 +1,4 @@
+use crate::primitives::EthereumProgram;
 use crate::utils::env_var::Error as EnvVarError;
 
 #[derive(Debug, Fail)]
@@ -23,18 +24,109 @@ pub enum Error {
     #[fail(display = "Invalid mnemonic phrase {}", _0)]
     InvalidMnemonicPhrase(String),
 
+    #[fail(display = "Invalid HD path template: {}", _0)]
+    InvalidHdPath(String),
+
+    #[fail(display = "Invalid sealer key {}", _0)]
+    InvalidSealerKey(String),
+
+    #[fail(display = "Invalid node host, expected [name@]host[:port]: {}", _0)]
+    InvalidNodeHost(String),
+
+    #[fail(
+        display = "Node keys of {:?} can not be derived from NODE_KEY_SEED, miners take their sealer keys",
+        _0
+    )]
+    UnsupportedNodeKeySeed(EthereumProgram),
+
     #[fail(display = "Invalid consensus engine type: {}", _0)]
     InvalidConsensusEngineType(String),
 
+    #[fail(
+        display = "Invalid consensus engine parameter `{}` of {}: {}",
+        key, source, reason
+    )]
+    InvalidEngineParameter {
+        source: String,
+        key: String,
+        reason: String,
+    },
+
+    #[fail(
+        display = "Consensus engine {} is not supported by {:?}",
+        engine, program
+    )]
+    UnsupportedConsensusEngine {
+        program: EthereumProgram,
+        engine: String,
+    },
+
     #[fail(display = "Invalid gas limit value: {}", _0)]
     InvalidGasLimitValue(String),
 
     #[fail(display = "Invalid minimum gas limit value: {}", _0)]
     InvalidMinimumGasLimitValue(String),
 
+    #[fail(display = "TOML error: {}", _0)]
+    TomlDe(toml::de::Error),
+
+    #[fail(display = "YAML error: {}", _0)]
+    SerdeYaml(serde_yaml::Error),
+
+    #[fail(display = "Unsupported format of network config file: {}", _0)]
+    UnsupportedNetworkConfigFormat(String),
+
+    #[fail(display = "Network setting is not present: {}", _0)]
+    MissingNetworkSetting(String),
+
+    #[fail(display = "Invalid network setting {}: {}", name, value)]
+    InvalidNetworkSetting { name: String, value: String },
+
+    #[fail(display = "Invalid Parity chain specification: {}", _0)]
+    InvalidParityChainSpec(String),
+
+    #[fail(display = "Invalid fork schedule: {}", _0)]
+    InvalidForkSchedule(String),
+
+    #[fail(display = "Unknown or unschedulable EIP: {}", _0)]
+    UnknownEip(String),
+
+    #[fail(display = "Invalid validator set: {}", _0)]
+    InvalidValidatorSet(String),
+
+    #[fail(
+        display = "Account state of {:?} conflicts with builtin precompiled contract",
+        _0
+    )]
+    ConflictingBuiltinAccount(ethereum_types::Address),
+
+    #[fail(display = "Genesis state can not be computed offline: {}", _0)]
+    UncomputableGenesisState(String),
+
     #[fail(display = "Invalid account state data: {}", _0)]
     InvalidAccountStateData(String),
 
+    #[fail(display = "Contract artifact is not found: {}", _0)]
+    ContractArtifactNotFound(String),
+
+    #[fail(display = "Invalid contract artifact: {}", _0)]
+    InvalidContractArtifact(String),
+
+    #[fail(display = "Invalid constructor arguments: {}", _0)]
+    InvalidConstructorArguments(String),
+
+    #[fail(
+        display = "Constructor of genesis account {:?} is not supported by {:?}",
+        address, program
+    )]
+    UnsupportedConstructor {
+        program: EthereumProgram,
+        address: ethereum_types::Address,
+    },
+
+    #[fail(display = "Invalid storage variable: {}", _0)]
+    InvalidStorageVariable(String),
+
     #[fail(
         display = "Failed to generate private key from seed {:?} and path {}",
         seed, path
@@ -60,6 +152,18 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<toml::de::Error> for Error {
+    fn from(error: toml::de::Error) -> Error {
+        Error::TomlDe(error)
+    }
+}
+
+impl From<serde_yaml::Error> for Error {
+    fn from(error: serde_yaml::Error) -> Error {
+        Error::SerdeYaml(error)
+    }
+}
+
 impl From<ethsign::Error> for Error {
     fn from(error: ethsign::Error) -> Error {
         Error::EthSign(error)
@@ -1,23 +1,49 @@
-use ethereum_types::{Address, U256};
-use ethsign::SecretKey;
-use hdwallet::mnemonic::{Language, Mnemonic};
+use ethereum_types::{Address, H256, U256};
 use serde_json;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use crate::utils::{self, env_var::from_env};
+use crate::utils::{self, rlp, trie};
 
+use super::builtin;
+use super::engine_parameters::{
+    AuraParameters, CliqueParameters, EngineParameters, EthashParameters, Ibft2Parameters,
+    TendermintParameters,
+};
 use super::error::Error;
-use super::{generate_keypair_with_index, AccountState, ConsensusEngine};
+use super::{
+    validator_addresses_from_env, AccountState, BlockReward, ConsensusEngine, EthereumProgram,
+    ForkSchedule, HardFork, KeyPair, NetworkConfig, SealerMnemonic, TestAccounts, ValidatorSet,
+};
+
+/// length of extra vanity prefix in Clique extraData
+const CLIQUE_EXTRA_VANITY_LENGTH: usize = 32;
+
+/// length of extra seal suffix in Clique extraData
+const CLIQUE_EXTRA_SEAL_LENGTH: usize = 65;
+
+/// nonce of Ethash genesis seal in Parity spec.json
+const PARITY_ETHASH_NONCE: u64 = 0x42;
+
+/// maximum size of extraData in block headers, extraData of genesis block is bounded by it and
+/// it is the length of Clique and IBFT 2.0 vanity
+const MAXIMUM_EXTRA_DATA_SIZE: usize = 32;
 
 lazy_static! {
     static ref DEFAULT_NETWORK_ID: U256 = U256::from(0xab23);
-    static ref DEFAULT_BLOCK_REWARD: U256 = U256::from(5) * U256::from(10).pow(18.into());
     static ref PARITY_DEFAULT_SEAL: serde_json::Value = json!({
         "authorityRound": {
             "step": "0x0",
             "signature": "0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"}});
+    static ref PARITY_ETHASH_SEAL: serde_json::Value = json!({
+        "ethereum": {
+            "nonce": format!("0x{:016x}", PARITY_ETHASH_NONCE),
+            "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000"}});
+    /// mixHash which identifies IBFT 2.0 blocks
+    static ref IBFT2_MIX_HASH: H256 =
+        H256::from_str("63746963616c2062797a616e74696e65206661756c7420746f6c6572616e6365")
+            .expect("IBFT 2.0 mixHash is valid; qed");
 }
 
 #[derive(Debug, Clone)]
@@ -25,9 +51,21 @@ pub struct EthereumChainSpec {
     /// name of Ethereum Blockchain
     pub name: String,
 
-    /// network ID of Ethereum Blockchain
+    /// network ID of Ethereum Blockchain, identifies the network in devp2p
     pub network_id: U256,
 
+    /// EIP-155 chain ID of transaction signatures, same as network ID unless it is given
+    pub chain_id: U256,
+
+    /// timestamp of genesis block in seconds
+    pub genesis_timestamp: u64,
+
+    /// extraData of genesis block, vanity of Clique and IBFT 2.0 extraData
+    pub extra_data: Vec<u8>,
+
+    /// beneficiary of genesis block
+    pub author: Address,
+
     /// Minimum gas limit of a block
     pub min_gas_limit: U256,
 
@@ -37,6 +75,12 @@ pub struct EthereumChainSpec {
     /// consensus engine type and its parameters
     pub consensus_engine: ConsensusEngine,
 
+    /// Ethereum program which runs the network, decides the format of chain specification
+    pub program: EthereumProgram,
+
+    /// activation blocks of hard forks and EIPs
+    pub fork_schedule: ForkSchedule,
+
     pub account_states: HashMap<Address, AccountState>,
 }
 
@@ -45,12 +89,28 @@ impl Default for EthereumChainSpec {
         EthereumChainSpec {
             name: "ethereum".to_owned(),
             network_id: U256::from(0x1234),
+            chain_id: U256::from(0x1234),
+            genesis_timestamp: 0,
+            extra_data: vec![],
+            author: Address::zero(),
             min_gas_limit: U256::from(0x1388),
             genesis_block_gas_limit: U256::from(5) * U256::from(10).pow(U256::from(18)),
             consensus_engine: ConsensusEngine::ParityAura {
                 block_period: 5,
-                block_reward: U256::from(5) * U256::from(10).pow(U256::from(18)),
-                validators: vec![],
+                block_reward: BlockReward::Constant(
+                    U256::from(5) * U256::from(10).pow(U256::from(18)),
+                ),
+                validators: ValidatorSet::List(vec![]),
+                maximum_uncle_count: None,
+                empty_steps_transition: None,
+                maximum_empty_steps: None,
+                block_reward_contract_address: None,
+                block_reward_contract_transition: None,
+            },
+            program: EthereumProgram::Parity,
+            fork_schedule: ForkSchedule {
+                wasm_activation: Some(0),
+                ..Default::default()
             },
             account_states: Default::default(),
         }
@@ -58,10 +118,24 @@ impl Default for EthereumChainSpec {
 }
 
 impl EthereumChainSpec {
-    fn validators_from_env() -> Result<Vec<Address>, Error> {
-        let seed = from_env("SEALER_MNEMONIC_PHRASE")?;
-        let miner_count: usize = from_env("MINER_COUNT")?.parse()?;
-        let keypairs = keypair_from_sealer_mnemonic(&seed, miner_count)?;
+    fn validators_from_env(miner_count: Option<usize>) -> Result<Vec<Address>, Error> {
+        if let Some(addresses) = validator_addresses_from_env()? {
+            return match miner_count {
+                None => Ok(addresses),
+                Some(count) if count <= addresses.len() => Ok(addresses[..count].to_vec()),
+                Some(count) => Err(Error::InvalidNetworkSetting {
+                    name: "VALIDATOR_ADDRESSES".to_owned(),
+                    value: format!("{} addresses for {} miners", addresses.len(), count),
+                }),
+            };
+        }
+
+        let sealer_mnemonic = SealerMnemonic::from_env()?;
+        let miner_count = match miner_count {
+            Some(miner_count) => miner_count,
+            None => return Err(Error::MissingNetworkSetting("minerCount".to_owned())),
+        };
+        let keypairs = keypair_from_sealer_mnemonic(&sealer_mnemonic, miner_count)?;
         let validators = keypairs
             .iter()
             .map(|sec| Address::from(*sec.public().address()))
@@ -69,216 +143,783 @@ impl EthereumChainSpec {
         Ok(validators)
     }
 
-    fn account_states_from_env() -> Result<HashMap<Address, AccountState>, Error> {
-        let account_states_file_path = match from_env("ACCOUNT_STATES_FILE") {
-            Ok(file_path) => PathBuf::from(file_path),
-            Err(_) => return Ok(HashMap::default()),
+    /// give validators of genesis their intrinsic balance, validators of later transitions are
+    /// not funded, so that transitions can be scheduled without changing genesis state
+    fn fund_validators(
+        account_states: &mut HashMap<Address, AccountState>,
+        validators: &[Address],
+        balance: U256,
+    ) {
+        for validator_address in validators {
+            account_states.insert(
+                *validator_address,
+                AccountState {
+                    balance: Some(balance),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    /// deploy code of block reward contract into `account_states`, the contract must exist in
+    /// genesis if no code is given
+    fn deploy_block_reward_contract(
+        source: &str,
+        parameters: &AuraParameters,
+        account_states: &mut HashMap<Address, AccountState>,
+    ) -> Result<(), Error> {
+        let invalid = |key: &str, reason: &str| Error::InvalidEngineParameter {
+            source: source.to_owned(),
+            key: key.to_owned(),
+            reason: reason.to_owned(),
+        };
+
+        let address = match parameters.block_reward_contract_address {
+            Some(address) => address,
+            None if parameters.block_reward_contract_transition.is_some()
+                || parameters.block_reward_contract_code.is_some() =>
+            {
+                return Err(invalid(
+                    "blockRewardContractAddress",
+                    "address of block reward contract is absent",
+                ));
+            }
+            None => return Ok(()),
         };
 
-        let file = std::fs::OpenOptions::new()
-            .read(true)
-            .open(account_states_file_path)?;
-
-        let raw_value: serde_json::Value = serde_json::from_reader(&file)?;
-        match raw_value {
-            serde_json::Value::Object(states) => {
-                Ok(states
-                    .into_iter()
-                    .fold(HashMap::default(), |mut states, (address, state)| {
-                        let address = match Address::from_str(utils::clean_0x(&address)) {
-                            Ok(addr) => addr,
-                            Err(_) => {
-                                println!("invalid ethereum address: {}", address);
-                                warn!("invalid ethereum address: {}", address);
-                                return states;
-                            }
-                        };
-
-                        states.insert(address, AccountState::from_json_value(&state));
-                        states
-                    }))
+        let account = account_states.entry(address).or_default();
+        if let Some(ref code) = parameters.block_reward_contract_code {
+            if utils::hex_to_bytes(code).map_or(true, |code| code.is_empty()) {
+                return Err(invalid("blockRewardContractCode", "invalid bytecode"));
             }
-            _ => Err(Error::InvalidAccountStateData(raw_value.to_string())),
+            account.code = Some(code.clone());
+        }
+        if account.code.is_none() && account.constructor.is_none() {
+            return Err(invalid(
+                "blockRewardContractCode",
+                "block reward contract has no code in genesis",
+            ));
         }
+        Ok(())
     }
 
-    pub fn from_env() -> Result<EthereumChainSpec, Error> {
-        let name = from_env("NETWORK_NAME")?;
+    fn account_states_from_json(
+        states: serde_json::Map<String, serde_json::Value>,
+    ) -> HashMap<Address, AccountState> {
+        states
+            .into_iter()
+            .fold(HashMap::default(), |mut states, (address, state)| {
+                let address = match Address::from_str(utils::clean_0x(&address)) {
+                    Ok(addr) => addr,
+                    Err(_) => {
+                        warn!("invalid ethereum address: {}", address);
+                        return states;
+                    }
+                };
+
+                states.insert(address, AccountState::from_json_value(&state));
+                states
+            })
+    }
+
+    /// chain specification from network config file overridden by environment variables,
+    /// NETWORK_CONFIG_FILE is used if no file is given
+    pub fn from_file_and_env(
+        network_config_file: Option<PathBuf>,
+    ) -> Result<EthereumChainSpec, Error> {
+        Self::from_network_config(NetworkConfig::from_file_and_env(network_config_file)?)
+    }
 
-        let network_id = utils::maybe_u256(
-            &from_env("NETWORK_ID").unwrap_or(format!("0x{:x}", DEFAULT_NETWORK_ID.clone())),
-        )
-        .unwrap_or(DEFAULT_NETWORK_ID.clone());
+    pub fn from_network_config(config: NetworkConfig) -> Result<EthereumChainSpec, Error> {
+        let test_accounts = TestAccounts::from_network_config(&config)?;
 
-        let genesis_block_gas_limit = {
-            let raw_value = from_env("GENESIS_BLOCK_GAS_LIMIT")?;
-            match utils::maybe_u256(&raw_value) {
-                Some(v) => v,
-                None => return Err(Error::InvalidGasLimitValue(raw_value)),
+        let name = match config.name {
+            Some(name) => name,
+            None => return Err(Error::MissingNetworkSetting("name".to_owned())),
+        };
+
+        let network_id = config
+            .network_id
+            .map(|id| id.0)
+            .unwrap_or(*DEFAULT_NETWORK_ID);
+        let chain_id = config.chain_id.map(|id| id.0).unwrap_or(network_id);
+
+        let genesis_timestamp = match config.genesis_timestamp {
+            Some(timestamp) if timestamp.0 > U256::from(u64::max_value()) => {
+                return Err(Error::InvalidNetworkSetting {
+                    name: "genesisTimestamp".to_owned(),
+                    value: timestamp.0.to_string(),
+                });
             }
+            Some(timestamp) => timestamp.0.low_u64(),
+            None => 0,
         };
 
-        let min_gas_limit = {
-            let raw_value = from_env("MIN_GAS_LIMIT").unwrap_or("0x1388".to_owned());
-            match utils::maybe_u256(&raw_value) {
-                Some(v) => v,
-                None => return Err(Error::InvalidMinimumGasLimitValue(raw_value)),
+        let extra_data = match config.extra_data {
+            Some(extra_data) => {
+                utils::hex_to_bytes(&extra_data).ok_or_else(|| Error::InvalidNetworkSetting {
+                    name: "extraData".to_owned(),
+                    value: extra_data.clone(),
+                })?
             }
+            None => vec![],
         };
+        let author = config.author.unwrap_or_default();
 
-        let mut account_states = Self::account_states_from_env()?;
+        let genesis_block_gas_limit = match config.genesis_block_gas_limit {
+            Some(gas_limit) => gas_limit.0,
+            None => {
+                return Err(Error::MissingNetworkSetting(
+                    "genesisBlockGasLimit".to_owned(),
+                ))
+            }
+        };
 
-        let consensus_engine = {
-            use serde_json::Value as JsonValue;
+        let min_gas_limit = config
+            .min_gas_limit
+            .map(|gas_limit| gas_limit.0)
+            .unwrap_or_else(|| U256::from(0x1388));
 
-            let sealer_intrinsic_balance =
-                utils::maybe_u256(&from_env("SEALER_INTRINSIC_BALANCE").unwrap_or("0".to_owned()))
-                    .unwrap_or_default();
+        let mut fork_schedule = config.forks.unwrap_or_default();
 
-            let engine = from_env("CONSENSUS_ENGINE")?;
-            match engine.to_lowercase().as_ref() {
+        let mut account_states = Self::account_states_from_json(config.account_states);
+        if !config.genesis_contracts.is_empty() {
+            let artifacts_dir = config
+                .artifacts_dir
+                .ok_or_else(|| Error::MissingNetworkSetting("artifactsDir".to_owned()))?;
+            for (address, contract) in &config.genesis_contracts {
+                let address = Address::from_str(utils::clean_0x(address)).map_err(|_| {
+                    Error::InvalidNetworkSetting {
+                        name: "genesisContracts".to_owned(),
+                        value: address.clone(),
+                    }
+                })?;
+                let state = account_states.remove(&address).unwrap_or_default();
+                account_states.insert(address, contract.account_state(&artifacts_dir, state)?);
+            }
+        }
+        let miner_count = config.miner_count;
+
+        let consensus = match config.consensus {
+            Some(consensus) => consensus,
+            None => return Err(Error::MissingNetworkSetting("consensus".to_owned())),
+        };
+
+        let consensus_engine = {
+            let sealer_intrinsic_balance = config
+                .sealer_intrinsic_balance
+                .map(|balance| balance.0)
+                .unwrap_or_default();
+
+            let source = consensus
+                .parameters_source
+                .clone()
+                .unwrap_or_else(|| "consensus.parameters".to_owned());
+            let engine_parameters = consensus.parameters.unwrap_or_else(|| json!({}));
+            match consensus.engine.to_lowercase().as_ref() {
                 "ethash" => {
-                    let engine_parameters: JsonValue =
-                        serde_json::from_str(from_env("ETHASH_CONSENSUS_PARAMETERS")?.as_str())?;
-                    let genesis_difficulty = utils::maybe_u256_from_json_value(
-                        &engine_parameters["genesisBlockDifficulty"],
-                    )
-                    .unwrap_or(U256::from(16384));
-                    ConsensusEngine::Ethash { genesis_difficulty }
+                    let parameters = EthashParameters::parse(&source, &engine_parameters)?;
+
+                    ConsensusEngine::Ethash {
+                        genesis_difficulty: parameters.genesis_block_difficulty.0,
+                        minimum_difficulty: parameters.minimum_difficulty.0,
+                        difficulty_bound_divisor: parameters.difficulty_bound_divisor.0,
+                        duration_limit: parameters.duration_limit,
+                        block_reward: parameters.block_reward,
+                        homestead_transition: parameters.homestead_transition,
+                        bomb_defuse_transition: parameters.bomb_defuse_transition,
+                    }
                 }
                 "aura" => {
-                    let engine_parameters: JsonValue =
-                        serde_json::from_str(from_env("AURA_CONSENSUS_PARAMETERS")?.as_str())?;
-                    let block_period = engine_parameters["blockPeriod"].as_u64().unwrap_or(7);
-                    let block_reward =
-                        utils::maybe_u256_from_json_value(&engine_parameters["blockReward"])
-                            .unwrap_or(DEFAULT_BLOCK_REWARD.clone());
-
-                    let validators = Self::validators_from_env()?;
-                    for validator_address in validators.iter() {
-                        account_states.insert(
-                            validator_address.clone(),
-                            AccountState {
-                                balance: Some(sealer_intrinsic_balance),
-                                ..Default::default()
-                            },
-                        );
-                    }
+                    let parameters = AuraParameters::parse(&source, &engine_parameters)?;
+                    Self::deploy_block_reward_contract(&source, &parameters, &mut account_states)?;
+
+                    let validators = ValidatorSet::from_engine_parameter(
+                        &parameters.validators,
+                        &|count: Option<usize>| Self::validators_from_env(count.or(miner_count)),
+                        &mut account_states,
+                    )?;
+                    Self::fund_validators(
+                        &mut account_states,
+                        &validators.validators_at(0),
+                        sealer_intrinsic_balance,
+                    );
 
                     ConsensusEngine::ParityAura {
-                        block_period,
-                        block_reward,
+                        block_period: parameters.block_period,
+                        block_reward: parameters.block_reward,
                         validators,
+                        maximum_uncle_count: parameters.maximum_uncle_count,
+                        empty_steps_transition: parameters.empty_steps_transition,
+                        maximum_empty_steps: parameters.maximum_empty_steps,
+                        block_reward_contract_address: parameters.block_reward_contract_address,
+                        block_reward_contract_transition: parameters
+                            .block_reward_contract_transition,
                     }
                 }
                 "tendermint" => {
-                    let engine_parameters: JsonValue = serde_json::from_str(
-                        from_env("TENDERMINT_CONSENSUS_PARAMETERS")?.as_str(),
-                    )?;
+                    let parameters = TendermintParameters::parse(&source, &engine_parameters)?;
 
-                    let propose_timeout = engine_parameters["proposeTimeout"]
-                        .as_u64()
-                        .unwrap_or(10000);
-                    let prevote_timeout = engine_parameters["prevoteTimeout"]
-                        .as_u64()
-                        .unwrap_or(10000);
-                    let precommit_timeout = engine_parameters["precommitTimeout"]
-                        .as_u64()
-                        .unwrap_or(10000);
-                    let commit_timeout =
-                        engine_parameters["commitTimeout"].as_u64().unwrap_or(10000);
-
-                    let block_reward =
-                        utils::maybe_u256_from_json_value(&engine_parameters["blockReward"])
-                            .unwrap_or(DEFAULT_BLOCK_REWARD.clone());
-
-                    let validators = Self::validators_from_env()?;
-                    for validator_address in validators.iter() {
-                        account_states.insert(
-                            validator_address.clone(),
-                            AccountState {
-                                balance: Some(sealer_intrinsic_balance),
-                                ..Default::default()
-                            },
-                        );
-                    }
+                    let validators = ValidatorSet::from_engine_parameter(
+                        &parameters.validators,
+                        &|count: Option<usize>| Self::validators_from_env(count.or(miner_count)),
+                        &mut account_states,
+                    )?;
+                    Self::fund_validators(
+                        &mut account_states,
+                        &validators.validators_at(0),
+                        sealer_intrinsic_balance,
+                    );
 
                     ConsensusEngine::ParityTendermint {
-                        propose_timeout,
-                        prevote_timeout,
-                        precommit_timeout,
-                        commit_timeout,
-                        block_reward,
+                        propose_timeout: parameters.propose_timeout,
+                        prevote_timeout: parameters.prevote_timeout,
+                        precommit_timeout: parameters.precommit_timeout,
+                        commit_timeout: parameters.commit_timeout,
+                        block_reward: parameters.block_reward,
                         validators,
                     }
                 }
                 "clique" => {
-                    let engine_parameters: JsonValue =
-                        serde_json::from_str(from_env("CLIQUE_CONSENSUS_PARAMETERS")?.as_str())?;
-                    let block_period = engine_parameters["blockPeriod"].as_u64().unwrap_or(7);
-                    let block_reward =
-                        utils::maybe_u256_from_json_value(&engine_parameters["blockReward"])
-                            .unwrap_or(DEFAULT_BLOCK_REWARD.clone());
-
-                    let validators = Self::validators_from_env()?;
-                    for validator_address in validators.iter() {
-                        account_states.insert(
-                            validator_address.clone(),
-                            AccountState {
-                                balance: Some(sealer_intrinsic_balance),
-                                ..Default::default()
-                            },
-                        );
-                    }
+                    let parameters = CliqueParameters::parse(&source, &engine_parameters)?;
+
+                    let validators = Self::validators_from_env(miner_count)?;
+                    Self::fund_validators(
+                        &mut account_states,
+                        &validators,
+                        sealer_intrinsic_balance,
+                    );
 
                     ConsensusEngine::GethClique {
-                        block_period,
-                        block_reward,
+                        block_period: parameters.block_period,
+                        epoch: parameters.epoch,
+                        block_reward: parameters.block_reward.0,
+                        validators,
+                    }
+                }
+                "ibft2" => {
+                    let parameters = Ibft2Parameters::parse(&source, &engine_parameters)?;
+
+                    let validators = Self::validators_from_env(miner_count)?;
+                    Self::fund_validators(
+                        &mut account_states,
+                        &validators,
+                        sealer_intrinsic_balance,
+                    );
+
+                    ConsensusEngine::BesuIbft2 {
+                        block_period: parameters.block_period,
+                        epoch: parameters.epoch,
+                        request_timeout: parameters.request_timeout,
+                        block_reward: parameters.block_reward.0,
                         validators,
                     }
                 }
                 _ => {
-                    return Err(Error::InvalidConsensusEngineType(engine));
+                    return Err(Error::InvalidConsensusEngineType(consensus.engine));
                 }
             }
         };
 
-        Ok(EthereumChainSpec {
+        if let Some(test_accounts) = test_accounts {
+            for address in test_accounts.addresses()? {
+                account_states.entry(address).or_default().balance = Some(test_accounts.balance);
+            }
+        }
+
+        builtin::check_builtin_conflicts(&account_states)?;
+
+        let program = match config.program {
+            Some(program) => EthereumProgram::from_str(&program)?,
+            None => consensus_engine.program(),
+        };
+
+        if !program.supports(&consensus_engine) {
+            return Err(Error::UnsupportedConsensusEngine {
+                program,
+                engine: consensus.engine,
+            });
+        }
+
+        for contract in config.genesis_contracts.values() {
+            contract.validate(program)?;
+        }
+
+        fork_schedule.validate(program)?;
+        if let EthereumProgram::Parity | EthereumProgram::Nethermind = program {
+            // WebAssembly contracts are activated from genesis unless scheduled otherwise
+            fork_schedule.wasm_activation = fork_schedule.wasm_activation.or(Some(0));
+        }
+
+        let spec = EthereumChainSpec {
+            name,
+            network_id,
+            chain_id,
+            genesis_timestamp,
+            extra_data,
+            author,
+            min_gas_limit,
+            genesis_block_gas_limit,
+            consensus_engine,
+            program,
+            fork_schedule,
+            account_states,
+        };
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// chain specification from spec.json of Parity Ethereum, builtin accounts are skipped unless
+    /// account states are merged into them
+    pub fn from_parity_spec(spec: &serde_json::Value) -> Result<EthereumChainSpec, Error> {
+        let invalid = |reason: &str| Error::InvalidParityChainSpec(reason.to_owned());
+
+        let name = spec["name"]
+            .as_str()
+            .ok_or_else(|| invalid("name"))?
+            .to_owned();
+
+        let params = &spec["params"];
+        let network_id = utils::maybe_u256_from_json_value(&params["networkID"])
+            .ok_or_else(|| invalid("params.networkID"))?;
+        let chain_id = match params["chainID"] {
+            serde_json::Value::Null => network_id,
+            ref chain_id => utils::maybe_u256_from_json_value(chain_id)
+                .ok_or_else(|| invalid("params.chainID"))?,
+        };
+        let min_gas_limit = utils::maybe_u256_from_json_value(&params["minGasLimit"])
+            .ok_or_else(|| invalid("params.minGasLimit"))?;
+
+        let genesis = &spec["genesis"];
+        let genesis_block_gas_limit = utils::maybe_u256_from_json_value(&genesis["gasLimit"])
+            .ok_or_else(|| invalid("genesis.gasLimit"))?;
+        let genesis_timestamp = match genesis["timestamp"] {
+            serde_json::Value::Null => 0,
+            ref timestamp => utils::maybe_u256_from_json_value(timestamp)
+                .filter(|timestamp| *timestamp <= U256::from(u64::max_value()))
+                .ok_or_else(|| invalid("genesis.timestamp"))?
+                .low_u64(),
+        };
+        let extra_data = match genesis["extraData"] {
+            serde_json::Value::Null => vec![],
+            ref extra_data => extra_data
+                .as_str()
+                .and_then(utils::hex_to_bytes)
+                .ok_or_else(|| invalid("genesis.extraData"))?,
+        };
+        let author = match genesis["author"] {
+            serde_json::Value::Null => Address::zero(),
+            ref author => author
+                .as_str()
+                .and_then(|author| Address::from_str(utils::clean_0x(author)).ok())
+                .ok_or_else(|| invalid("genesis.author"))?,
+        };
+
+        let consensus_engine =
+            ConsensusEngine::from_parity_spec(&spec["engine"], genesis, &spec["accounts"])?;
+
+        let mut account_states = HashMap::default();
+        if let Some(accounts) = spec["accounts"].as_object() {
+            for (address, account) in accounts {
+                let mut account = account.clone();
+                let is_builtin = account
+                    .as_object_mut()
+                    .and_then(|account| account.remove("builtin"))
+                    .is_some();
+
+                let address =
+                    Address::from_str(utils::clean_0x(address)).map_err(|_| invalid(address))?;
+                let state: AccountState = serde_json::from_value(account.clone())
+                    .map_err(|_| Error::InvalidAccountStateData(account.to_string()))?;
+
+                // builtin accounts are generated, only states merged into them are kept
+                let builtin_default = AccountState {
+                    balance: Some(U256::one()),
+                    ..Default::default()
+                };
+                if is_builtin && state == builtin_default {
+                    continue;
+                }
+                account_states.insert(address, state);
+            }
+        }
+
+        let spec = EthereumChainSpec {
             name,
             network_id,
+            chain_id,
+            genesis_timestamp,
+            extra_data,
+            author,
             min_gas_limit,
             genesis_block_gas_limit,
             consensus_engine,
+            program: EthereumProgram::Parity,
+            fork_schedule: ForkSchedule::from_parity_params(params),
             account_states,
-        })
+        };
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// chain specification from spec.json file of Parity Ethereum
+    pub fn from_parity_spec_file(file_path: &Path) -> Result<EthereumChainSpec, Error> {
+        let file = std::fs::OpenOptions::new().read(true).open(file_path)?;
+        Self::from_parity_spec(&serde_json::from_reader(&file)?)
+    }
+
+    /// check identifiers and genesis header fields which are not validated on parsing
+    fn validate(&self) -> Result<(), Error> {
+        let invalid = |name: &str, value: String| Error::InvalidNetworkSetting {
+            name: name.to_owned(),
+            value,
+        };
+
+        // Go-Ethereum and Besu read identifiers as 64 bit integers
+        let max_id = U256::from(u64::max_value());
+        if self.network_id.is_zero() || self.network_id > max_id {
+            return Err(invalid("networkId", self.network_id.to_string()));
+        }
+        if self.chain_id.is_zero() || self.chain_id > max_id {
+            return Err(invalid("chainId", self.chain_id.to_string()));
+        }
+
+        if self.extra_data.len() > MAXIMUM_EXTRA_DATA_SIZE {
+            return Err(invalid(
+                "extraData",
+                format!(
+                    "{} exceeds maximumExtraDataSize {}",
+                    utils::bytes_to_0xhex(&self.extra_data),
+                    MAXIMUM_EXTRA_DATA_SIZE
+                ),
+            ));
+        }
+
+        // Tendermint of Parity Ethereum pays a single reward for all blocks
+        if let ConsensusEngine::ParityTendermint {
+            block_reward: BlockReward::Schedule(ref schedule),
+            ..
+        } = self.consensus_engine
+        {
+            return Err(invalid(
+                "blockReward",
+                format!("{} rewards, tendermint takes a constant", schedule.len()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// whether both chain specifications describe the same genesis block, so that a running
+    /// network can switch between them
+    pub fn has_same_genesis(&self, other: &EthereumChainSpec) -> bool {
+        self.name == other.name
+            && self.network_id == other.network_id
+            && self.chain_id == other.chain_id
+            && self.genesis_timestamp == other.genesis_timestamp
+            && self.extra_data == other.extra_data
+            && self.author == other.author
+            && self.genesis_block_gas_limit == other.genesis_block_gas_limit
+            && self.program == other.program
+            && self.consensus_engine.genesis_validators()
+                == other.consensus_engine.genesis_validators()
+            && self.account_states == other.account_states
     }
 
     pub fn validators(&self) -> Option<Vec<Address>> {
         self.consensus_engine.validators()
     }
 
-    pub fn as_json(&self) -> serde_json::Value {
-        let (engine, seal) = match self.consensus_engine {
-            ConsensusEngine::ParityAura {
+    /// whether the network runs with spec.json of Parity Ethereum, which holds builtin accounts
+    fn uses_parity_spec(&self) -> bool {
+        match self.program {
+            EthereumProgram::Parity | EthereumProgram::Nethermind => true,
+            EthereumProgram::GoEthereum | EthereumProgram::Besu => false,
+        }
+    }
+
+    /// accounts of genesis state, builtin accounts of Parity spec.json hold 1 wei by default
+    fn genesis_accounts(&self) -> HashMap<Address, AccountState> {
+        let mut accounts = self.account_states.clone();
+        if self.uses_parity_spec() {
+            for address in builtin::parity_builtin_addresses(&self.fork_schedule) {
+                accounts
+                    .entry(address)
+                    .or_default()
+                    .balance
+                    .get_or_insert_with(U256::one);
+            }
+        }
+        accounts
+    }
+
+    /// root hash of genesis state trie
+    pub fn genesis_state_root(&self) -> Result<H256, Error> {
+        let entries = self
+            .genesis_accounts()
+            .iter()
+            .map(|(address, state)| {
+                Ok((
+                    utils::keccak256(&address[..]).to_vec(),
+                    state.genesis_trie_rlp(address)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(trie::trie_root(entries))
+    }
+
+    /// hash of genesis block header
+    pub fn genesis_hash(&self) -> Result<H256, Error> {
+        let difficulty = match self.consensus_engine {
+            ConsensusEngine::Ethash {
+                genesis_difficulty, ..
+            } => genesis_difficulty,
+            _ => U256::one(),
+        };
+
+        let (extra_data, seal) = match (self.uses_parity_spec(), &self.consensus_engine) {
+            (true, ConsensusEngine::Ethash { .. }) => (
+                utils::bytes_to_0xhex(&self.extra_data),
+                vec![
+                    rlp::encode_bytes(&H256::zero()[..]),
+                    rlp::encode_bytes(&PARITY_ETHASH_NONCE.to_be_bytes()),
+                ],
+            ),
+            // step and signature of authorityRound seal
+            (true, _) => (
+                utils::bytes_to_0xhex(&self.extra_data),
+                vec![
+                    rlp::encode_u256(&U256::zero()),
+                    rlp::encode_bytes(&[0u8; 65]),
+                ],
+            ),
+            (false, ConsensusEngine::GethClique { validators, .. }) => (
+                clique_extra_data(&self.extra_data, validators),
+                vec![
+                    rlp::encode_bytes(&H256::zero()[..]),
+                    rlp::encode_bytes(&[0u8; 8]),
+                ],
+            ),
+            (false, ConsensusEngine::BesuIbft2 { validators, .. }) => (
+                ibft2_extra_data(&self.extra_data, validators),
+                vec![
+                    rlp::encode_bytes(&IBFT2_MIX_HASH[..]),
+                    rlp::encode_bytes(&[0u8; 8]),
+                ],
+            ),
+            (false, _) => return Err(self.unsupported_engine()),
+        };
+        let extra_data =
+            utils::hex_to_bytes(&extra_data).expect("extraData is generated as hex string; qed");
+
+        let mut header = vec![
+            rlp::encode_bytes(&H256::zero()[..]),
+            // hash of empty ommer list
+            rlp::encode_bytes(&utils::keccak256(&rlp::encode_list(&[]))[..]),
+            rlp::encode_bytes(&self.author[..]),
+            rlp::encode_bytes(&self.genesis_state_root()?[..]),
+            rlp::encode_bytes(&trie::empty_trie_root()[..]),
+            rlp::encode_bytes(&trie::empty_trie_root()[..]),
+            rlp::encode_bytes(&[0u8; 256]),
+            rlp::encode_u256(&difficulty),
+            rlp::encode_u256(&U256::zero()),
+            rlp::encode_u256(&self.genesis_block_gas_limit),
+            rlp::encode_u256(&U256::zero()),
+            rlp::encode_u256(&U256::from(self.genesis_timestamp)),
+            rlp::encode_bytes(&extra_data),
+        ];
+        header.extend(seal);
+
+        Ok(utils::keccak256(&rlp::encode_list(&header)))
+    }
+
+    /// chain specification in the format of the Ethereum program which runs the network
+    pub fn as_json(&self) -> Result<serde_json::Value, Error> {
+        match self.program {
+            // Nethermind reads chain specification in the format of Parity Ethereum
+            EthereumProgram::Parity | EthereumProgram::Nethermind => self.as_parity_spec(),
+            EthereumProgram::GoEthereum => self.as_geth_genesis(),
+            EthereumProgram::Besu => self.as_besu_genesis(),
+        }
+    }
+
+    /// genesis.json for Go-Ethereum
+    pub fn as_geth_genesis(&self) -> Result<serde_json::Value, Error> {
+        let (engine, extra_data) = match self.consensus_engine {
+            ConsensusEngine::GethClique {
                 block_period,
+                epoch,
+                ref validators,
+                ..
+            } => (
+                json!({
+                    "clique": {
+                        "period": block_period,
+                        "epoch": epoch
+                    }
+                }),
+                clique_extra_data(&self.extra_data, validators),
+            ),
+            _ => return Err(self.unsupported_engine()),
+        };
+
+        self.genesis_json(engine, extra_data, H256::zero())
+    }
+
+    /// genesis.json for Hyperledger Besu
+    pub fn as_besu_genesis(&self) -> Result<serde_json::Value, Error> {
+        let (engine, extra_data, mix_hash) = match self.consensus_engine {
+            ConsensusEngine::GethClique {
+                block_period,
+                epoch,
+                ref validators,
+                ..
+            } => (
+                json!({
+                    "clique": {
+                        "blockperiodseconds": block_period,
+                        "epochlength": epoch
+                    }
+                }),
+                clique_extra_data(&self.extra_data, validators),
+                H256::zero(),
+            ),
+            ConsensusEngine::BesuIbft2 {
+                block_period,
+                epoch,
+                request_timeout,
                 block_reward,
                 ref validators,
             } => (
                 json!({
-                    "authorityRound": {
+                    "ibft2": {
+                        "blockperiodseconds": block_period,
+                        "epochlength": epoch,
+                        "requesttimeoutseconds": request_timeout,
+                        "blockreward": utils::to_0xhex(&block_reward)
+                    }
+                }),
+                ibft2_extra_data(&self.extra_data, validators),
+                *IBFT2_MIX_HASH,
+            ),
+            _ => return Err(self.unsupported_engine()),
+        };
+
+        self.genesis_json(engine, extra_data, mix_hash)
+    }
+
+    /// genesis.json shared by Go-Ethereum and Hyperledger Besu
+    fn genesis_json(
+        &self,
+        engine: serde_json::Value,
+        extra_data: String,
+        mix_hash: H256,
+    ) -> Result<serde_json::Value, Error> {
+        let mut config = json!({
+            "chainId": self.chain_id.as_u64(),
+        });
+        if let Some(config) = config.as_object_mut() {
+            config.extend(self.fork_schedule.geth_config());
+            if let Some(engine) = engine.as_object() {
+                config.extend(engine.clone());
+            }
+        }
+
+        let mut alloc = serde_json::Map::new();
+        for (address, state) in &self.account_states {
+            let account =
+                state
+                    .as_geth_alloc_json()
+                    .ok_or_else(|| Error::UnsupportedConstructor {
+                        program: self.program,
+                        address: *address,
+                    })?;
+            alloc.insert(utils::to_0xhex(address), account);
+        }
+
+        Ok(json!({
+            "config": config,
+            "nonce": "0x0",
+            "timestamp": utils::to_0xhex(&self.genesis_timestamp),
+            "extraData": extra_data,
+            "gasLimit": utils::to_0xhex(&self.genesis_block_gas_limit),
+            "difficulty": "0x1",
+            "mixHash": utils::to_0xhex(&mix_hash),
+            "coinbase": utils::to_0xhex(&self.author),
+            "number": "0x0",
+            "gasUsed": "0x0",
+            "parentHash": utils::to_0xhex(&H256::zero()),
+            "alloc": alloc
+        }))
+    }
+
+    /// spec.json for Parity Ethereum
+    pub fn as_parity_spec(&self) -> Result<serde_json::Value, Error> {
+        let (engine, seal) = match self.consensus_engine {
+            ConsensusEngine::Ethash {
+                minimum_difficulty,
+                difficulty_bound_divisor,
+                duration_limit,
+                ref block_reward,
+                homestead_transition,
+                bomb_defuse_transition,
+                ..
+            } => (
+                json!({
+                    "Ethash": {
                         "params": {
-                            "stepDuration": block_period.to_string(),
-                            "blockReward": block_reward.to_string(),
-                            "validators": {
-                                "list": validators
-                            }
+                            "minimumDifficulty": utils::to_0xhex(&minimum_difficulty),
+                            "difficultyBoundDivisor": utils::to_0xhex(&difficulty_bound_divisor),
+                            "durationLimit": utils::to_0xhex(&duration_limit),
+                            "blockReward": block_reward.as_json(),
+                            "homesteadTransition": homestead_transition,
+                            "eip100bTransition": self
+                                .fork_schedule
+                                .hard_fork_block(HardFork::Byzantium),
+                            "bombDefuseTransition": bomb_defuse_transition
                         }
                     }
                 }),
-                PARITY_DEFAULT_SEAL.clone(),
+                PARITY_ETHASH_SEAL.clone(),
             ),
+            ConsensusEngine::ParityAura {
+                block_period,
+                ref block_reward,
+                ref validators,
+                maximum_uncle_count,
+                empty_steps_transition,
+                maximum_empty_steps,
+                block_reward_contract_address,
+                block_reward_contract_transition,
+            } => {
+                let mut params = json!({
+                    "stepDuration": block_period.to_string(),
+                    "blockReward": block_reward.as_json(),
+                    "validators": validators.as_parity_json()
+                });
+                if let Some(maximum_uncle_count) = maximum_uncle_count {
+                    params["maximumUncleCount"] = json!(maximum_uncle_count);
+                }
+                if let Some(empty_steps_transition) = empty_steps_transition {
+                    params["emptyStepsTransition"] = json!(empty_steps_transition);
+                }
+                if let Some(maximum_empty_steps) = maximum_empty_steps {
+                    params["maximumEmptySteps"] = json!(maximum_empty_steps);
+                }
+                if let Some(address) = block_reward_contract_address {
+                    params["blockRewardContractAddress"] = json!(utils::to_0xhex(&address));
+                }
+                if let Some(transition) = block_reward_contract_transition {
+                    params["blockRewardContractTransition"] = json!(transition);
+                }
+
+                (
+                    json!({ "authorityRound": { "params": params } }),
+                    PARITY_DEFAULT_SEAL.clone(),
+                )
+            }
             ConsensusEngine::ParityTendermint {
-                block_reward,
+                ref block_reward,
                 propose_timeout,
                 prevote_timeout,
                 precommit_timeout,
@@ -288,146 +929,133 @@ impl EthereumChainSpec {
                 json! ({
                     "tendermint": {
                         "params": {
-                            "blockReward": block_reward.to_string(),
+                            "blockReward": block_reward.as_json(),
                             "timeoutPropose": propose_timeout,
                             "timeoutPrevote": prevote_timeout,
                             "timeoutPrecommit": precommit_timeout,
                             "timeoutCommit": commit_timeout,
-                            "validators": {
-                                "list": validators
-                            }
+                            "validators": validators.as_parity_json()
                         }
                     }
                 }),
                 PARITY_DEFAULT_SEAL.clone(),
             ),
-            _ => {
-                unimplemented!();
-            }
+            _ => return Err(self.unsupported_engine()),
+        };
+
+        let difficulty = match self.consensus_engine {
+            ConsensusEngine::Ethash {
+                genesis_difficulty, ..
+            } => genesis_difficulty,
+            _ => U256::one(),
         };
 
         let mut spec = json!({
             "name": self.name,
             "genesis": {
-                "difficulty": "0x1",
+                "difficulty": utils::to_0xhex(&difficulty),
                 "gasLimit": utils::to_0xhex(&self.genesis_block_gas_limit),
+                "timestamp": utils::to_0xhex(&self.genesis_timestamp),
+                "extraData": utils::bytes_to_0xhex(&self.extra_data),
+                "author": utils::to_0xhex(&self.author),
                 "seal": seal
             } ,
             "params": {
-                "maximumExtraDataSize": "0x20",
+                "maximumExtraDataSize": utils::to_0xhex(&MAXIMUM_EXTRA_DATA_SIZE),
                 "minGasLimit": utils::to_0xhex(&self.min_gas_limit),
                 "gasLimitBoundDivisor": "0x400",
                 "networkID": utils::to_0xhex(&self.network_id),
+                "chainID": utils::to_0xhex(&self.chain_id),
                 "maxCodeSize": 24576,
                 "maxCodeSizeTransition": 0,
                 "maxTransactionSize": usize::max_value(),
                 "validateChainIdTransition": 0,
-                "validateReceiptsTransition": 0,
-                "eip1014Transition": 0,
-                "eip1052Transition": 0,
-                "eip1283DisableTransition": 0,
-                "eip1283Transition": 0,
-                "eip140Transition": 0,
-                "eip145Transition": 0,
-                "eip150Transition": 0,
-                "eip155Transition": 0,
-                "eip160Transition": 0,
-                "eip161abcTransition": 0,
-                "eip161dTransition": 0,
-                "eip211Transition": 0,
-                "eip214Transition": 0,
-                "eip658Transition": 0,
-                "eip98Transition": 0,
-                "kip4Transition": 0,
-                "kip6Transition": 0,
-                "wasmActivationTransition": 0
+                "validateReceiptsTransition": 0
             },
             "engine": engine,
-            "accounts": {
-                "0x0000000000000000000000000000000000000001": {
-                    "balance": "1",
-                    "builtin": {
-                        "name": "ecrecover",
-                        "pricing": {
-                            "linear": {
-                                "base": 3000,
-                                "word": 0
-                            }
-                        }
-                    }
-                },
-                "0x0000000000000000000000000000000000000002": {
-                    "balance": "1",
-                    "builtin": {
-                        "name": "sha256",
-                        "pricing": {
-                            "linear": {
-                                "base": 60,
-                                "word": 12
-                            }
-                        }
-                    }
-                },
-                "0x0000000000000000000000000000000000000003": {
-                    "balance": "1",
-                    "builtin": {
-                        "name": "ripemd160",
-                        "pricing": {
-                            "linear": {
-                                "base": 600,
-                                "word": 120
-                            }
+            "accounts": builtin::parity_builtin_accounts(&self.fork_schedule)
+        });
+
+        if let Some(params) = spec["params"].as_object_mut() {
+            params.extend(self.fork_schedule.parity_params());
+        }
+
+        // merge account states into builtin accounts, conflicts are rejected on construction
+        if let Some(spec_accounts) = spec["accounts"].as_object_mut() {
+            for (address, state) in self.account_states.iter() {
+                let address = utils::to_0xhex(address);
+                let state =
+                    serde_json::to_value(&state).expect("AccountState is serializable; qed");
+                match spec_accounts
+                    .get_mut(&address)
+                    .and_then(|a| a.as_object_mut())
+                {
+                    Some(builtin_account) => {
+                        if let serde_json::Value::Object(state) = state {
+                            builtin_account.extend(state);
                         }
                     }
-                },
-                "0x0000000000000000000000000000000000000004": {
-                    "balance": "1",
-                    "builtin": {
-                        "name": "identity",
-                        "pricing": {
-                            "linear": {
-                                "base": 15,
-                                "word": 3
-                            }
-                        }
+                    None => {
+                        spec_accounts.insert(address, state);
                     }
                 }
             }
-        });
+        }
+        Ok(spec)
+    }
 
-        // insert account states
-        spec["accounts"] =
-            self.account_states
-                .iter()
-                .fold(json!({}), |mut spec_accounts, (address, state)| {
-                    let address = utils::to_0xhex(address);
-                    let state =
-                        serde_json::to_value(&state).expect("AccountState is serializable; qed");
-                    spec_accounts
-                        .as_object_mut()
-                        .expect("spec_accounts is an object; qed")
-                        .insert(address, state);
-                    spec_accounts
-                });
-        spec
+    /// error of consensus engine which the Ethereum program of the network does not run
+    fn unsupported_engine(&self) -> Error {
+        Error::UnsupportedConsensusEngine {
+            program: self.program,
+            engine: self.consensus_engine.name().to_owned(),
+        }
     }
 }
 
-pub fn keypair_from_sealer_mnemonic(
-    sealer_mnemonic: &str,
-    sealer_count: usize,
-) -> Result<Vec<SecretKey>, Error> {
-    let mnemonic = match Mnemonic::try_from(Language::English, sealer_mnemonic) {
-        Ok(m) => m,
-        Err(_) => {
-            return Err(Error::InvalidMnemonicPhrase(sealer_mnemonic.to_owned()));
-        }
-    };
+/// vanity of Clique and IBFT 2.0 extraData, extraData of chain specification padded with zeros
+fn extra_vanity(extra_data: &[u8]) -> [u8; CLIQUE_EXTRA_VANITY_LENGTH] {
+    let mut vanity = [0u8; CLIQUE_EXTRA_VANITY_LENGTH];
+    vanity[..extra_data.len()].copy_from_slice(extra_data);
+    vanity
+}
+
+/// extraData of Clique genesis block: vanity, sorted signer addresses and an empty seal
+fn clique_extra_data(extra_data: &[u8], validators: &[Address]) -> String {
+    let mut validators = validators.to_vec();
+    validators.sort();
 
-    let mut keypairs = Vec::with_capacity(sealer_count);
-    for i in 0..sealer_count {
-        keypairs.push(generate_keypair_with_index(&mnemonic, i)?);
+    let mut extra_data = utils::bytes_to_0xhex(&extra_vanity(extra_data));
+    for validator in validators.iter() {
+        extra_data.push_str(&format!("{:x}", validator));
     }
+    extra_data.push_str(&"00".repeat(CLIQUE_EXTRA_SEAL_LENGTH));
+    extra_data
+}
 
-    Ok(keypairs)
+/// RLP encoded extraData of IBFT 2.0 genesis block: vanity, validators, no vote, round 0 and no
+/// seals
+fn ibft2_extra_data(extra_data: &[u8], validators: &[Address]) -> String {
+    let mut validators = validators.to_vec();
+    validators.sort();
+
+    let validators: Vec<_> = validators
+        .iter()
+        .map(|validator| rlp::encode_bytes(&validator[..]))
+        .collect();
+
+    utils::bytes_to_0xhex(&rlp::encode_list(&[
+        rlp::encode_bytes(&extra_vanity(extra_data)),
+        rlp::encode_list(&validators),
+        rlp::encode_bytes(&[]),
+        rlp::encode_bytes(&[0u8; 4]),
+        rlp::encode_list(&[]),
+    ]))
+}
+
+pub fn keypair_from_sealer_mnemonic(
+    sealer_mnemonic: &SealerMnemonic,
+    sealer_count: usize,
+) -> Result<Vec<KeyPair>, Error> {
+    sealer_mnemonic.secret_keys(sealer_count)
 }
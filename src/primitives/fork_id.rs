@@ -0,0 +1,166 @@
+use ethereum_types::H256;
+use serde::{de, Deserialize, Deserializer, Serializer};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeSet;
+
+use super::ForkSchedule;
+use crate::utils;
+
+/// EIP-2124 fork identifier: checksum of genesis hash and passed fork blocks, and the next
+/// fork block or 0 if none is scheduled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForkId {
+    #[serde(
+        serialize_with = "serialize_hash",
+        deserialize_with = "deserialize_hash"
+    )]
+    pub hash: u32,
+    pub next: u64,
+}
+
+impl ForkId {
+    /// fork identifier of chain at `head`, `fork_blocks` must be sorted without genesis block
+    pub fn new(genesis_hash: &H256, fork_blocks: &[u64], head: u64) -> ForkId {
+        let mut hash = utils::crc32_update(0, &genesis_hash[..]);
+        for block in fork_blocks {
+            if *block > head {
+                return ForkId { hash, next: *block };
+            }
+            hash = utils::crc32_update(hash, &block.to_be_bytes());
+        }
+
+        ForkId { hash, next: 0 }
+    }
+
+    /// blocks at which rules of chain change, read from the fork transitions of Parity spec.json
+    /// or the fork blocks of Go-Ethereum genesis.json, sorted without genesis block
+    pub fn fork_blocks(chainspec: &JsonValue) -> Vec<u64> {
+        let (values, keys): (_, Vec<_>) = if chainspec.get("params").is_some() {
+            (
+                &chainspec["params"],
+                ForkSchedule::parity_transition_keys().collect(),
+            )
+        } else {
+            (
+                &chainspec["config"],
+                ForkSchedule::geth_config_keys().collect(),
+            )
+        };
+        let mut blocks: BTreeSet<u64> = keys
+            .into_iter()
+            .filter_map(|key| utils::maybe_u64_from_json_value(&values[key]))
+            .collect();
+
+        blocks.remove(&0);
+        blocks.into_iter().collect()
+    }
+}
+
+impl std::fmt::Display for ForkId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:08x}/{}", self.hash, self.next)
+    }
+}
+
+fn serialize_hash<S>(hash: &u32, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&format!("0x{:08x}", hash))
+}
+
+fn deserialize_hash<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let hash = String::deserialize(deserializer)?;
+    u32::from_str_radix(utils::clean_0x(&hash), 16)
+        .map_err(|_| de::Error::custom(format!("invalid fork hash: {}", hash)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn mainnet_genesis_hash() -> H256 {
+        H256::from_str("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3")
+            .expect("genesis hash is valid")
+    }
+
+    fn fork_id(hash: u32, next: u64) -> ForkId {
+        ForkId { hash, next }
+    }
+
+    // mainnet vectors of EIP-2124, forks until Petersburg
+    #[test]
+    fn fork_id_of_mainnet_until_petersburg() {
+        let genesis_hash = mainnet_genesis_hash();
+        let fork_blocks = [
+            1_150_000, 1_920_000, 2_463_000, 2_675_000, 4_370_000, 7_280_000,
+        ];
+        let vectors = [
+            (0, fork_id(0xfc64_ec04, 1_150_000)),
+            (1_149_999, fork_id(0xfc64_ec04, 1_150_000)),
+            (1_150_000, fork_id(0x97c2_c34c, 1_920_000)),
+            (1_919_999, fork_id(0x97c2_c34c, 1_920_000)),
+            (1_920_000, fork_id(0x91d1_f948, 2_463_000)),
+            (2_462_999, fork_id(0x91d1_f948, 2_463_000)),
+            (2_463_000, fork_id(0x7a64_da13, 2_675_000)),
+            (2_674_999, fork_id(0x7a64_da13, 2_675_000)),
+            (2_675_000, fork_id(0x3edd_5b10, 4_370_000)),
+            (4_369_999, fork_id(0x3edd_5b10, 4_370_000)),
+            (4_370_000, fork_id(0xa00b_c324, 7_280_000)),
+            (7_279_999, fork_id(0xa00b_c324, 7_280_000)),
+            (7_280_000, fork_id(0x668d_b0af, 0)),
+            (7_987_396, fork_id(0x668d_b0af, 0)),
+        ];
+
+        for (head, expected) in vectors.iter() {
+            assert_eq!(ForkId::new(&genesis_hash, &fork_blocks, *head), *expected);
+        }
+    }
+
+    // mainnet vectors of EIP-2124, forks until Muir Glacier
+    #[test]
+    fn fork_id_of_mainnet_until_muir_glacier() {
+        let genesis_hash = mainnet_genesis_hash();
+        let fork_blocks = [
+            1_150_000, 1_920_000, 2_463_000, 2_675_000, 4_370_000, 7_280_000, 9_069_000, 9_200_000,
+        ];
+        let vectors = [
+            (7_987_396, fork_id(0x668d_b0af, 9_069_000)),
+            (9_068_999, fork_id(0x668d_b0af, 9_069_000)),
+            (9_069_000, fork_id(0x879d_6e30, 9_200_000)),
+            (9_199_999, fork_id(0x879d_6e30, 9_200_000)),
+            (9_200_000, fork_id(0xe029_e991, 0)),
+            (10_000_000, fork_id(0xe029_e991, 0)),
+        ];
+
+        for (head, expected) in vectors.iter() {
+            assert_eq!(ForkId::new(&genesis_hash, &fork_blocks, *head), *expected);
+        }
+    }
+
+    #[test]
+    fn fork_blocks_of_geth_genesis_are_unique() {
+        let genesis = json!({
+            "config": {
+                "chainId": 1,
+                "homesteadBlock": 1_150_000,
+                "eip150Block": 2_463_000,
+                "eip155Block": 2_675_000,
+                "eip158Block": 2_675_000,
+                "byzantiumBlock": 4_370_000,
+                "constantinopleBlock": 7_280_000,
+                "petersburgBlock": 7_280_000,
+                "istanbulBlock": 9_069_000
+            }
+        });
+
+        assert_eq!(
+            ForkId::fork_blocks(&genesis),
+            vec![1_150_000, 2_463_000, 2_675_000, 4_370_000, 7_280_000, 9_069_000]
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::BTreeMap;
+
+use crate::utils;
+
+use super::{error::Error, EthereumProgram};
+
+/// hard forks which can be scheduled, in order of activation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HardFork {
+    Byzantium,
+    Constantinople,
+    Petersburg,
+    Istanbul,
+    Berlin,
+}
+
+impl HardFork {
+    pub const ALL: [HardFork; 5] = [
+        HardFork::Byzantium,
+        HardFork::Constantinople,
+        HardFork::Petersburg,
+        HardFork::Istanbul,
+        HardFork::Berlin,
+    ];
+
+    fn geth_config_key(self) -> &'static str {
+        match self {
+            HardFork::Byzantium => "byzantiumBlock",
+            HardFork::Constantinople => "constantinopleBlock",
+            HardFork::Petersburg => "petersburgBlock",
+            HardFork::Istanbul => "istanbulBlock",
+            HardFork::Berlin => "berlinBlock",
+        }
+    }
+}
+
+/// EIPs which are activated before all scheduled hard forks
+const GENESIS_PARITY_TRANSITIONS: &[&str] = &[
+    "eip98Transition",
+    "eip150Transition",
+    "eip155Transition",
+    "eip160Transition",
+    "eip161abcTransition",
+    "eip161dTransition",
+];
+
+/// EIPs which can be scheduled separately: EIP number, hard fork which includes it and its
+/// transition parameter of Parity
+const SCHEDULED_EIPS: &[(u32, HardFork, &str)] = &[
+    (140, HardFork::Byzantium, "eip140Transition"),
+    (211, HardFork::Byzantium, "eip211Transition"),
+    (214, HardFork::Byzantium, "eip214Transition"),
+    (658, HardFork::Byzantium, "eip658Transition"),
+    (145, HardFork::Constantinople, "eip145Transition"),
+    (1014, HardFork::Constantinople, "eip1014Transition"),
+    (1052, HardFork::Constantinople, "eip1052Transition"),
+    (1283, HardFork::Constantinople, "eip1283Transition"),
+    (1344, HardFork::Istanbul, "eip1344Transition"),
+    (1706, HardFork::Istanbul, "eip1706Transition"),
+    (1884, HardFork::Istanbul, "eip1884Transition"),
+    (2028, HardFork::Istanbul, "eip2028Transition"),
+    (2200, HardFork::Istanbul, "eip1283ReenableTransition"),
+    (2929, HardFork::Berlin, "eip2929Transition"),
+    (2930, HardFork::Berlin, "eip2930Transition"),
+];
+
+/// Petersburg only removes EIP-1283 which is added by Constantinople
+const PETERSBURG_PARITY_TRANSITION: &str = "eip1283DisableTransition";
+
+/// transitions of Parity which activate WebAssembly contracts
+const WASM_PARITY_TRANSITIONS: &[&str] = &[
+    "wasmActivationTransition",
+    "kip4Transition",
+    "kip6Transition",
+];
+
+/// fork blocks of Go-Ethereum which are activated before all scheduled hard forks
+const GENESIS_GETH_BLOCKS: &[&str] = &[
+    "homesteadBlock",
+    "eip150Block",
+    "eip155Block",
+    "eip158Block",
+];
+
+/// Activation blocks of hard forks and EIPs, a hard fork includes all hard forks before it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ForkSchedule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byzantium: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constantinople: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub petersburg: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub istanbul: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub berlin: Option<u64>,
+
+    /// activation blocks of single EIPs keyed by EIP number like "1884" or "eip1884",
+    /// they override activation blocks of hard forks
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub eips: BTreeMap<String, u64>,
+
+    /// activation block of WebAssembly contracts of Parity, from genesis if not present, which
+    /// Go-Ethereum and Besu do not support
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm_activation: Option<u64>,
+}
+
+impl Default for ForkSchedule {
+    fn default() -> ForkSchedule {
+        ForkSchedule {
+            byzantium: None,
+            constantinople: None,
+            petersburg: Some(0),
+            istanbul: None,
+            berlin: None,
+            eips: BTreeMap::default(),
+            wasm_activation: None,
+        }
+    }
+}
+
+impl ForkSchedule {
+    fn scheduled_block(&self, hard_fork: HardFork) -> Option<u64> {
+        match hard_fork {
+            HardFork::Byzantium => self.byzantium,
+            HardFork::Constantinople => self.constantinople,
+            HardFork::Petersburg => self.petersburg,
+            HardFork::Istanbul => self.istanbul,
+            HardFork::Berlin => self.berlin,
+        }
+    }
+
+    fn scheduled_block_mut(&mut self, hard_fork: HardFork) -> &mut Option<u64> {
+        match hard_fork {
+            HardFork::Byzantium => &mut self.byzantium,
+            HardFork::Constantinople => &mut self.constantinople,
+            HardFork::Petersburg => &mut self.petersburg,
+            HardFork::Istanbul => &mut self.istanbul,
+            HardFork::Berlin => &mut self.berlin,
+        }
+    }
+
+    /// activation block of hard fork, which is activated no later than the hard forks after it
+    pub fn hard_fork_block(&self, hard_fork: HardFork) -> Option<u64> {
+        HardFork::ALL
+            .iter()
+            .filter(|fork| **fork >= hard_fork)
+            .filter_map(|fork| self.scheduled_block(*fork))
+            .min()
+    }
+
+    /// activation block of EIP, the override of EIP takes precedence over its hard fork
+    pub fn eip_block(&self, eip: u32) -> Option<u64> {
+        let hard_fork = SCHEDULED_EIPS
+            .iter()
+            .find(|(number, _, _)| *number == eip)
+            .map(|(_, hard_fork, _)| *hard_fork)?;
+
+        match self.eip_overrides().ok()?.get(&eip) {
+            Some(block) => Some(*block),
+            None => self.hard_fork_block(hard_fork),
+        }
+    }
+
+    fn eip_overrides(&self) -> Result<BTreeMap<u32, u64>, Error> {
+        self.eips
+            .iter()
+            .map(|(eip, block)| {
+                let number = eip.trim_start_matches("eip").trim_start_matches("EIP");
+                match number.parse::<u32>() {
+                    Ok(number) if SCHEDULED_EIPS.iter().any(|(n, _, _)| *n == number) => {
+                        Ok((number, *block))
+                    }
+                    _ => Err(Error::UnknownEip(eip.clone())),
+                }
+            })
+            .collect()
+    }
+
+    /// check order of hard forks, Go-Ethereum and Besu only schedule whole hard forks
+    pub fn validate(&self, program: EthereumProgram) -> Result<(), Error> {
+        self.eip_overrides()?;
+
+        match program {
+            EthereumProgram::GoEthereum | EthereumProgram::Besu => {
+                if !self.eips.is_empty() {
+                    return Err(Error::InvalidForkSchedule(format!(
+                        "activation blocks of single EIPs are not supported by {:?}",
+                        program
+                    )));
+                }
+                if self.wasm_activation.is_some() {
+                    return Err(Error::InvalidForkSchedule(format!(
+                        "WebAssembly activation is not supported by {:?}",
+                        program
+                    )));
+                }
+            }
+            EthereumProgram::Parity | EthereumProgram::Nethermind => {}
+        }
+
+        let mut last_fork: Option<(HardFork, u64)> = None;
+        for hard_fork in HardFork::ALL.iter() {
+            if let Some(block) = self.scheduled_block(*hard_fork) {
+                if let Some((last_fork, last_block)) = last_fork {
+                    if block < last_block {
+                        return Err(Error::InvalidForkSchedule(format!(
+                            "{:?} at block {} is before {:?} at block {}",
+                            hard_fork, block, last_fork, last_block
+                        )));
+                    }
+                }
+                last_fork = Some((*hard_fork, block));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// transition parameters of Parity spec.json
+    pub fn parity_params(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut params = serde_json::Map::new();
+
+        for key in GENESIS_PARITY_TRANSITIONS {
+            params.insert((*key).to_owned(), json!(0));
+        }
+
+        for (eip, _, key) in SCHEDULED_EIPS {
+            if let Some(block) = self.eip_block(*eip) {
+                params.insert((*key).to_owned(), json!(block));
+            }
+        }
+
+        if let Some(block) = self.hard_fork_block(HardFork::Petersburg) {
+            params.insert(PETERSBURG_PARITY_TRANSITION.to_owned(), json!(block));
+        }
+
+        if let Some(block) = self.wasm_activation {
+            for key in WASM_PARITY_TRANSITIONS {
+                params.insert((*key).to_owned(), json!(block));
+            }
+        }
+
+        params
+    }
+
+    /// fork blocks of `config` in Go-Ethereum genesis.json, overrides of single EIPs are not
+    /// expressible in Go-Ethereum and rejected by `validate`
+    pub fn geth_config(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut config = serde_json::Map::new();
+
+        for key in GENESIS_GETH_BLOCKS {
+            config.insert((*key).to_owned(), json!(0));
+        }
+
+        for hard_fork in HardFork::ALL.iter() {
+            if let Some(block) = self.hard_fork_block(*hard_fork) {
+                config.insert(hard_fork.geth_config_key().to_owned(), json!(block));
+            }
+        }
+
+        config
+    }
+
+    /// keys of `params` in Parity spec.json which are written by `parity_params`
+    pub fn parity_transition_keys() -> impl Iterator<Item = &'static str> {
+        GENESIS_PARITY_TRANSITIONS
+            .iter()
+            .cloned()
+            .chain(SCHEDULED_EIPS.iter().map(|(_, _, key)| *key))
+            .chain(std::iter::once(PETERSBURG_PARITY_TRANSITION))
+            .chain(WASM_PARITY_TRANSITIONS.iter().cloned())
+    }
+
+    /// keys of `config` in Go-Ethereum genesis.json which are written by `geth_config`
+    pub fn geth_config_keys() -> impl Iterator<Item = &'static str> {
+        GENESIS_GETH_BLOCKS.iter().cloned().chain(
+            HardFork::ALL
+                .iter()
+                .map(|hard_fork| hard_fork.geth_config_key()),
+        )
+    }
+
+    /// fork schedule from transition parameters of Parity spec.json
+    pub fn from_parity_params(params: &serde_json::Value) -> ForkSchedule {
+        let transition = |key: &str| utils::maybe_u64_from_json_value(&params[key]);
+
+        let mut schedule = ForkSchedule {
+            petersburg: transition(PETERSBURG_PARITY_TRANSITION),
+            wasm_activation: transition("wasmActivationTransition"),
+            ..Default::default()
+        };
+
+        // the earliest EIP of a hard fork is taken as its activation block
+        for hard_fork in HardFork::ALL.iter() {
+            if *hard_fork == HardFork::Petersburg {
+                continue;
+            }
+            *schedule.scheduled_block_mut(*hard_fork) = SCHEDULED_EIPS
+                .iter()
+                .filter(|(_, fork, _)| fork == hard_fork)
+                .filter_map(|(_, _, key)| transition(key))
+                .min();
+        }
+
+        for (eip, _, key) in SCHEDULED_EIPS {
+            if let Some(block) = transition(key) {
+                if Some(block) != schedule.eip_block(*eip) {
+                    schedule.eips.insert(eip.to_string(), block);
+                }
+            }
+        }
+
+        schedule
+    }
+}
@@ -0,0 +1,75 @@
+use ethsign::{Protected, SecretKey};
+
+/// secret key with its raw bytes, ethsign does not expose them but clients like Go-Ethereum and
+/// Besu take node keys in raw files
+pub struct KeyPair {
+    secret: [u8; 32],
+    secret_key: SecretKey,
+}
+
+impl KeyPair {
+    pub fn from_raw(secret: &[u8]) -> Result<KeyPair, ethsign::Error> {
+        let secret_key = SecretKey::from_raw(secret)?;
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(secret);
+        Ok(KeyPair {
+            secret: bytes,
+            secret_key,
+        })
+    }
+
+    /// raw bytes of secret key are recovered from a keystore of a single KDF round, which is the
+    /// only way ethsign gives them out
+    pub fn from_secret_key(secret_key: SecretKey) -> Result<KeyPair, ethsign::Error> {
+        let password = Protected::from("etherinit".as_bytes());
+        let crypto = secret_key.to_crypto(
+            &password,
+            std::num::NonZeroU32::new(1).expect("1 is none zero; qed"),
+        )?;
+        let secret = crypto.decrypt(&password)?;
+
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&secret);
+        Ok(KeyPair {
+            secret: bytes,
+            secret_key,
+        })
+    }
+
+    pub fn secret(&self) -> &[u8] {
+        &self.secret
+    }
+}
+
+impl std::ops::Deref for KeyPair {
+    type Target = SecretKey;
+
+    fn deref(&self) -> &SecretKey {
+        &self.secret_key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils;
+
+    #[test]
+    fn raw_bytes_are_recovered_from_secret_key() {
+        let secret = utils::hex_to_bytes(
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        )
+        .expect("secret is hex");
+        let secret_key = SecretKey::from_raw(&secret).expect("secret is valid");
+
+        let key_pair = KeyPair::from_secret_key(secret_key).expect("secret key is valid");
+        assert_eq!(key_pair.secret(), &secret[..]);
+        assert_eq!(
+            key_pair.public().address(),
+            KeyPair::from_raw(&secret)
+                .expect("secret is valid")
+                .public()
+                .address()
+        );
+    }
+}
@@ -1,29 +1,63 @@
-use ethsign::SecretKey;
+use ethereum_types::H256;
+use ethsign::{keyfile::KeyFile, SecretKey};
 use std::str::FromStr;
 
 mod account_state;
+mod block_reward;
+mod builtin;
 mod consensus_engine;
+mod contract_artifact;
+mod engine_parameters;
 mod enode_url;
 mod error;
 mod ethereum_chainspec;
+mod fork_id;
+mod fork_schedule;
+mod key_pair;
+mod network_config;
 mod node_info;
+mod node_key;
 mod node_role;
+mod sealer_key;
+mod sealer_mnemonic;
+mod test_accounts;
+mod validator_set;
 
 pub use self::account_state::AccountState;
+pub use self::block_reward::BlockReward;
 pub use self::consensus_engine::ConsensusEngine;
+pub use self::contract_artifact::{ContractArtifact, GenesisContract};
 pub use self::enode_url::{Error as EthereumNodeUrlError, EthereumNodeUrl};
 pub use self::error::Error;
-pub use self::ethereum_chainspec::EthereumChainSpec;
+pub use self::ethereum_chainspec::{keypair_from_sealer_mnemonic, EthereumChainSpec};
+pub use self::fork_id::ForkId;
+pub use self::fork_schedule::{ForkSchedule, HardFork};
+pub use self::key_pair::KeyPair;
+pub use self::network_config::NetworkConfig;
 pub use self::node_info::NodeInfo;
+pub use self::node_key::{node_id, NodeKeySeed};
 pub use self::node_role::NodeRole;
+pub use self::sealer_key::{validator_addresses_from_env, SealerKeySource};
+pub use self::sealer_mnemonic::SealerMnemonic;
+pub use self::test_accounts::TestAccounts;
+pub use self::validator_set::{ValidatorContract, ValidatorSet};
+
+/// HD path of keys derived from mnemonic, `{index}` is replaced by index of key
+pub const DEFAULT_HD_PATH: &str = "m/44'/60'/0'/0/{index}";
+
+/// PBKDF2 rounds of keystores written by etherinit, same as geth, scrypt is not offered since
+/// ethsign only encrypts keystores with PBKDF2
+pub const DEFAULT_KDF_ITERATIONS: u32 = 262_144;
 
 pub const DEFAULT_PARITY_GAS_CAP: &str = "10000000";
 pub const DEFAULT_PARITY_GAS_FLOOR_TARGET: &str = "8000000";
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EthereumProgram {
     Parity,
     GoEthereum,
+    Nethermind,
+    Besu,
 }
 
 impl FromStr for EthereumProgram {
@@ -32,17 +66,66 @@ impl FromStr for EthereumProgram {
         match s.to_lowercase().as_str() {
             "parity" | "parity-ethereum" | "parityethereum" => Ok(EthereumProgram::Parity),
             "geth" | "go-ethereum" | "goethereum" => Ok(EthereumProgram::GoEthereum),
+            "nethermind" | "nethermind-ethereum" | "nethermindethereum" => {
+                Ok(EthereumProgram::Nethermind)
+            }
+            "besu" | "hyperledger-besu" | "hyperledgerbesu" => Ok(EthereumProgram::Besu),
             _ => Err(Error::InvalidEthereumProgramName(s.to_owned())),
         }
     }
 }
 
+impl EthereumProgram {
+    pub fn supports(self, consensus_engine: &ConsensusEngine) -> bool {
+        match (self, consensus_engine) {
+            (EthereumProgram::Parity, ConsensusEngine::Ethash { .. })
+            | (EthereumProgram::Parity, ConsensusEngine::ParityAura { .. })
+            | (EthereumProgram::Parity, ConsensusEngine::ParityTendermint { .. })
+            | (EthereumProgram::GoEthereum, ConsensusEngine::GethClique { .. })
+            | (EthereumProgram::Nethermind, ConsensusEngine::Ethash { .. })
+            | (EthereumProgram::Nethermind, ConsensusEngine::ParityAura { .. })
+            | (EthereumProgram::Besu, ConsensusEngine::GethClique { .. })
+            | (EthereumProgram::Besu, ConsensusEngine::BesuIbft2 { .. }) => true,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EthereumSystemInfo {
+    pub program: EthereumProgram,
     pub consensus_engine: ConsensusEngine,
     pub miner_count: usize,
     pub node_count: usize,
+
+    /// network ID of devp2p, which is not part of genesis.json of Go-Ethereum and Besu
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_id: Option<u64>,
+
+    /// revision of chain specification, which increases with each chain specification served
+    #[serde(default)]
+    pub chainspec_revision: u64,
+
+    /// hash of genesis block, absent if it can not be computed offline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_hash: Option<H256>,
+
+    /// blocks at which rules of chain change, sorted without genesis block
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fork_blocks: Vec<u64>,
+
+    /// EIP-2124 fork identifier at genesis block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_id: Option<ForkId>,
+}
+
+impl EthereumSystemInfo {
+    /// EIP-2124 fork identifier at `head`, None if genesis hash is unknown
+    pub fn fork_id_at(&self, head: u64) -> Option<ForkId> {
+        self.genesis_hash
+            .map(|genesis_hash| ForkId::new(&genesis_hash, &self.fork_blocks, head))
+    }
 }
 
 use hdwallet::{
@@ -50,27 +133,80 @@ use hdwallet::{
     mnemonic::Mnemonic,
 };
 
-fn default_hdpath_with_index(index: u32) -> HDPath {
-    HDPath(vec![
-        ChildNumber::Hardened(44),
-        ChildNumber::Hardened(60),
-        ChildNumber::Hardened(0),
-        ChildNumber::Normal(0),
-        ChildNumber::Normal(index as u32),
-    ])
+/// BIP-32 path of key at `index` from a template like `m/44'/60'/0'/0/{index}`
+fn hdpath_with_index(template: &str, index: usize) -> Result<HDPath, Error> {
+    let invalid = || Error::InvalidHdPath(template.to_owned());
+    if !template.contains("{index}") {
+        return Err(invalid());
+    }
+
+    let path = template.replace("{index}", &index.to_string());
+    let mut components = path.split('/');
+    if components.next() != Some("m") {
+        return Err(invalid());
+    }
+
+    components
+        .map(|component| {
+            let (number, hardened) = match component {
+                _ if component.ends_with('\'') || component.ends_with('h') => {
+                    (&component[..component.len() - 1], true)
+                }
+                _ => (component, false),
+            };
+            match number.parse::<u32>() {
+                Ok(number) if number < 0x8000_0000 && hardened => Ok(ChildNumber::Hardened(number)),
+                Ok(number) if number < 0x8000_0000 => Ok(ChildNumber::Normal(number)),
+                _ => Err(invalid()),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(HDPath)
 }
 
-fn generate_keypair_with_index(
-    mnemonic: &Mnemonic,
-    sealer_index: usize,
-) -> Result<SecretKey, Error> {
-    let seed = mnemonic.seed("");
-    let path = default_hdpath_with_index(sealer_index as u32);
+/// keypair of BIP-32 path from seed of mnemonic with BIP-39 passphrase
+fn generate_keypair(mnemonic: &Mnemonic, passphrase: &str, path: HDPath) -> Result<KeyPair, Error> {
+    let seed = mnemonic.seed(passphrase);
     match hdpath::generate_keypair(&path, &seed) {
-        Ok(keypair) => Ok(keypair),
+        Ok(secret_key) => Ok(KeyPair::from_secret_key(secret_key)?),
         Err(_err) => Err(Error::FailedToGeneratePrivateKey {
             seed,
             path: format!("{:?}", path),
         }),
     }
 }
+
+/// keypair at `index` of HD path template
+fn generate_keypair_with_hdpath(
+    mnemonic: &Mnemonic,
+    passphrase: &str,
+    hdpath_template: &str,
+    index: usize,
+) -> Result<KeyPair, Error> {
+    generate_keypair(
+        mnemonic,
+        passphrase,
+        hdpath_with_index(hdpath_template, index)?,
+    )
+}
+
+/// V3 keystore of secret key, encrypted by passphrase with PBKDF2 of `iterations` rounds
+pub fn key_file(
+    secret_key: &SecretKey,
+    passphrase: &str,
+    id: String,
+    iterations: u32,
+) -> Result<KeyFile, Error> {
+    let passphrase = ethsign::Protected::from(passphrase.as_bytes());
+    Ok(KeyFile {
+        id,
+        version: 3,
+        crypto: secret_key.to_crypto(
+            &passphrase,
+            std::num::NonZeroU32::new(iterations.max(1)).expect("iterations is at least 1; qed"),
+        )?,
+        address: Some(ethsign::keyfile::Bytes(
+            secret_key.public().address().to_vec(),
+        )),
+    })
+}
@@ -0,0 +1,276 @@
+use ethereum_types::{Address, U256};
+use serde::{de, Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::utils::{self, env_var::from_env};
+
+use super::error::Error;
+use super::{ForkSchedule, GenesisContract};
+
+/// integer value written as number, decimal string or 0x-prefixed hex string
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity(pub U256);
+
+impl<'de> Deserialize<'de> for Quantity {
+    fn deserialize<D>(deserializer: D) -> Result<Quantity, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawQuantity {
+            Number(u64),
+            Text(String),
+        }
+
+        match RawQuantity::deserialize(deserializer)? {
+            RawQuantity::Number(n) => Ok(Quantity(U256::from(n))),
+            RawQuantity::Text(s) => utils::maybe_u256(&s)
+                .map(Quantity)
+                .ok_or_else(|| de::Error::custom(format!("invalid quantity: {}", s))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ConsensusConfig {
+    /// name of consensus engine, same as CONSENSUS_ENGINE
+    pub engine: String,
+
+    /// engine parameters, same as <ENGINE>_CONSENSUS_PARAMETERS
+    #[serde(default)]
+    pub parameters: Option<serde_json::Value>,
+
+    /// environment variable of engine parameters if they are overridden
+    #[serde(skip)]
+    pub parameters_source: Option<String>,
+}
+
+/// Declarative definition of an Ethereum network, every setting can be overridden by
+/// its environment variable
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NetworkConfig {
+    pub name: Option<String>,
+    pub network_id: Option<Quantity>,
+
+    /// EIP-155 chain ID, same as CHAIN_ID, network ID is used if absent
+    pub chain_id: Option<Quantity>,
+
+    /// timestamp of genesis block, same as GENESIS_TIMESTAMP
+    pub genesis_timestamp: Option<Quantity>,
+
+    /// hex encoded extraData of genesis block, same as GENESIS_EXTRA_DATA
+    pub extra_data: Option<String>,
+
+    /// beneficiary of genesis block, same as GENESIS_AUTHOR
+    pub author: Option<Address>,
+
+    pub genesis_block_gas_limit: Option<Quantity>,
+    pub min_gas_limit: Option<Quantity>,
+
+    /// name of Ethereum program, same as ETHEREUM_PROGRAM
+    pub program: Option<String>,
+    pub consensus: Option<ConsensusConfig>,
+
+    /// activation blocks of hard forks and EIPs, same as FORK_SCHEDULE
+    pub forks: Option<ForkSchedule>,
+
+    pub miner_count: Option<usize>,
+    pub sealer_intrinsic_balance: Option<Quantity>,
+
+    /// account states keyed by address, entries of ACCOUNT_STATES_FILE take precedence
+    #[serde(default)]
+    pub account_states: serde_json::Map<String, serde_json::Value>,
+
+    /// mnemonic of funded test accounts, same as FAUCET_MNEMONIC
+    pub faucet_mnemonic: Option<String>,
+    pub test_account_count: Option<usize>,
+    pub test_account_balance: Option<Quantity>,
+
+    /// HD path template of test accounts, same as TEST_ACCOUNT_HD_PATH
+    pub test_account_hd_path: Option<String>,
+
+    /// directory of Truffle, Hardhat or solc JSON artifacts, same as ACCOUNT_ARTIFACTS_DIR
+    pub artifacts_dir: Option<PathBuf>,
+
+    /// contracts deployed from artifacts keyed by address, entries of GENESIS_CONTRACTS_FILE
+    /// take precedence
+    #[serde(default)]
+    pub genesis_contracts: BTreeMap<String, GenesisContract>,
+}
+
+impl NetworkConfig {
+    /// network config file overridden by environment variables, NETWORK_CONFIG_FILE is used if
+    /// no file is given
+    pub fn from_file_and_env(network_config_file: Option<PathBuf>) -> Result<NetworkConfig, Error> {
+        let network_config_file = match network_config_file {
+            Some(file_path) => Some(file_path),
+            None => from_env("NETWORK_CONFIG_FILE").ok().map(PathBuf::from),
+        };
+
+        let network_config = match network_config_file {
+            Some(file_path) => NetworkConfig::from_file(&file_path)?,
+            None => NetworkConfig::default(),
+        };
+
+        network_config.override_with_env()
+    }
+
+    /// load network definition from TOML or YAML file, the format is decided by file extension
+    pub fn from_file(file_path: &Path) -> Result<NetworkConfig, Error> {
+        let content = std::fs::read_to_string(file_path)?;
+
+        match file_path.extension().and_then(std::ffi::OsStr::to_str) {
+            Some("toml") => Ok(toml::from_str(&content)?),
+            Some("yaml") | Some("yml") => Ok(serde_yaml::from_str(&content)?),
+            _ => Err(Error::UnsupportedNetworkConfigFormat(
+                file_path.to_string_lossy().into_owned(),
+            )),
+        }
+    }
+
+    /// override settings with environment variables which are present
+    pub fn override_with_env(mut self) -> Result<NetworkConfig, Error> {
+        if let Ok(name) = from_env("NETWORK_NAME") {
+            self.name = Some(name);
+        }
+
+        if let Ok(raw_value) = from_env("NETWORK_ID") {
+            self.network_id = Some(quantity_from_env("NETWORK_ID", raw_value)?);
+        }
+
+        if let Ok(raw_value) = from_env("CHAIN_ID") {
+            self.chain_id = Some(quantity_from_env("CHAIN_ID", raw_value)?);
+        }
+
+        if let Ok(raw_value) = from_env("GENESIS_TIMESTAMP") {
+            self.genesis_timestamp = Some(quantity_from_env("GENESIS_TIMESTAMP", raw_value)?);
+        }
+
+        if let Ok(extra_data) = from_env("GENESIS_EXTRA_DATA") {
+            self.extra_data = Some(extra_data);
+        }
+
+        if let Ok(raw_value) = from_env("GENESIS_AUTHOR") {
+            match Address::from_str(utils::clean_0x(&raw_value)) {
+                Ok(author) => self.author = Some(author),
+                Err(_) => {
+                    return Err(Error::InvalidNetworkSetting {
+                        name: "GENESIS_AUTHOR".to_owned(),
+                        value: raw_value,
+                    })
+                }
+            }
+        }
+
+        if let Ok(raw_value) = from_env("GENESIS_BLOCK_GAS_LIMIT") {
+            match utils::maybe_u256(&raw_value) {
+                Some(v) => self.genesis_block_gas_limit = Some(Quantity(v)),
+                None => return Err(Error::InvalidGasLimitValue(raw_value)),
+            }
+        }
+
+        if let Ok(raw_value) = from_env("MIN_GAS_LIMIT") {
+            match utils::maybe_u256(&raw_value) {
+                Some(v) => self.min_gas_limit = Some(Quantity(v)),
+                None => return Err(Error::InvalidMinimumGasLimitValue(raw_value)),
+            }
+        }
+
+        if let Ok(program) = from_env("ETHEREUM_PROGRAM") {
+            self.program = Some(program);
+        }
+
+        if let Ok(engine) = from_env("CONSENSUS_ENGINE") {
+            let parameters = match self.consensus {
+                // parameters of another engine are meaningless
+                Some(ref consensus) if consensus.engine.eq_ignore_ascii_case(&engine) => {
+                    consensus.parameters.clone()
+                }
+                _ => None,
+            };
+            self.consensus = Some(ConsensusConfig {
+                engine,
+                parameters,
+                parameters_source: None,
+            });
+        }
+
+        if let Some(ref mut consensus) = self.consensus {
+            let var_name = format!("{}_CONSENSUS_PARAMETERS", consensus.engine.to_uppercase());
+            if let Ok(raw_value) = from_env(&var_name) {
+                consensus.parameters = Some(serde_json::from_str(&raw_value).map_err(|err| {
+                    Error::InvalidNetworkSetting {
+                        name: var_name.clone(),
+                        value: err.to_string(),
+                    }
+                })?);
+                consensus.parameters_source = Some(var_name);
+            }
+        }
+
+        if let Ok(raw_value) = from_env("FORK_SCHEDULE") {
+            self.forks = Some(serde_json::from_str(&raw_value)?);
+        }
+
+        if let Ok(raw_value) = from_env("MINER_COUNT") {
+            self.miner_count = Some(raw_value.parse()?);
+        }
+
+        if let Ok(raw_value) = from_env("SEALER_INTRINSIC_BALANCE") {
+            self.sealer_intrinsic_balance =
+                Some(quantity_from_env("SEALER_INTRINSIC_BALANCE", raw_value)?);
+        }
+
+        if let Ok(file_path) = from_env("ACCOUNT_STATES_FILE") {
+            let file = std::fs::OpenOptions::new().read(true).open(file_path)?;
+
+            match serde_json::from_reader(&file)? {
+                serde_json::Value::Object(states) => self.account_states.extend(states),
+                raw_value => return Err(Error::InvalidAccountStateData(raw_value.to_string())),
+            }
+        }
+
+        if let Ok(phrase) = from_env("FAUCET_MNEMONIC") {
+            self.faucet_mnemonic = Some(phrase);
+        }
+
+        if let Ok(raw_value) = from_env("TEST_ACCOUNT_COUNT") {
+            self.test_account_count = Some(raw_value.parse()?);
+        }
+
+        if let Ok(raw_value) = from_env("TEST_ACCOUNT_BALANCE") {
+            self.test_account_balance = Some(quantity_from_env("TEST_ACCOUNT_BALANCE", raw_value)?);
+        }
+
+        if let Ok(template) = from_env("TEST_ACCOUNT_HD_PATH") {
+            self.test_account_hd_path = Some(template);
+        }
+
+        if let Ok(dir_path) = from_env("ACCOUNT_ARTIFACTS_DIR") {
+            self.artifacts_dir = Some(PathBuf::from(dir_path));
+        }
+
+        if let Ok(file_path) = from_env("GENESIS_CONTRACTS_FILE") {
+            let file = std::fs::OpenOptions::new().read(true).open(file_path)?;
+            let contracts: BTreeMap<String, GenesisContract> = serde_json::from_reader(&file)?;
+            self.genesis_contracts.extend(contracts);
+        }
+
+        Ok(self)
+    }
+}
+
+fn quantity_from_env(var_name: &str, raw_value: String) -> Result<Quantity, Error> {
+    match utils::maybe_u256(&raw_value) {
+        Some(v) => Ok(Quantity(v)),
+        None => Err(Error::InvalidNetworkSetting {
+            name: var_name.to_owned(),
+            value: raw_value,
+        }),
+    }
+}
@@ -0,0 +1,71 @@
+use ethsign::SecretKey;
+
+use crate::utils::{self, env_var::from_env};
+
+use super::{Error, EthereumNodeUrl, KeyPair};
+
+/// default port of devp2p
+const DEFAULT_NETWORK_PORT: u16 = 30303;
+
+/// seed of devp2p node keys, the node key of a node is derived from the seed and its name, so
+/// that enode URLs of a network are known before its nodes start
+#[derive(Clone)]
+pub struct NodeKeySeed(String);
+
+impl std::fmt::Debug for NodeKeySeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NodeKeySeed")
+    }
+}
+
+impl NodeKeySeed {
+    /// seed from NODE_KEY_SEED, None if it is absent
+    pub fn from_env() -> Option<NodeKeySeed> {
+        from_env("NODE_KEY_SEED").ok().map(NodeKeySeed)
+    }
+
+    /// node key of node named `node_name`, keccak256 of seed and name is hashed again until it
+    /// is a valid secret key
+    pub fn node_key(&self, node_name: &str) -> KeyPair {
+        let mut hash = utils::keccak256(format!("{}/{}", self.0, node_name).as_bytes());
+        loop {
+            if let Ok(node_key) = KeyPair::from_raw(&hash[..]) {
+                return node_key;
+            }
+            hash = utils::keccak256(&hash[..]);
+        }
+    }
+
+    /// enode URL of node from `[name@]host[:port]`, the name is the first label of host if it
+    /// is absent, like the hostname of a pod behind a headless service
+    pub fn enode_url(&self, node: &str) -> Result<EthereumNodeUrl, Error> {
+        let invalid = || Error::InvalidNodeHost(node.to_owned());
+
+        let (name, address) = match node.find('@') {
+            Some(at) => (Some(&node[..at]), &node[at + 1..]),
+            None => (None, node),
+        };
+        let (host, port) = match address.rfind(':') {
+            Some(colon) => (
+                &address[..colon],
+                address[colon + 1..].parse().map_err(|_| invalid())?,
+            ),
+            None => (address, DEFAULT_NETWORK_PORT),
+        };
+        let name = name.unwrap_or_else(|| host.split('.').next().unwrap_or(host));
+        if name.is_empty() || host.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(EthereumNodeUrl {
+            node_id: node_id(&self.node_key(name)),
+            host: host.to_owned(),
+            port,
+        })
+    }
+}
+
+/// node ID of enode URL, the uncompressed public key in hex without prefix
+pub fn node_id(node_key: &SecretKey) -> String {
+    utils::bytes_to_0xhex(node_key.public().bytes())[2..].to_owned()
+}
@@ -1,12 +1,12 @@
-use ethsign::SecretKey;
-
-use hdwallet::mnemonic::Mnemonic;
+use super::{KeyPair, SealerKeySource};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub enum NodeRole {
     Miner {
         index: usize,
-        sealer_mnemonic: Mnemonic,
+        sealer_key: SealerKeySource,
+        /// block from which the sealer key is a validator, None if not known yet
+        activation_block: Option<u64>,
         parity_gas_floor_target: Option<String>,
         parity_gas_cap: Option<String>,
     },
@@ -17,7 +17,14 @@ pub enum NodeRole {
 impl std::fmt::Debug for NodeRole {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            NodeRole::Miner { index, .. } => write!(f, "Miner(index: {})", index),
+            NodeRole::Miner {
+                index,
+                activation_block,
+                ..
+            } => match activation_block {
+                Some(block) => write!(f, "Miner(index: {}, activation block: {})", index, block),
+                None => write!(f, "Miner(index: {})", index),
+            },
             NodeRole::Transactor => write!(f, "Transactor"),
             NodeRole::Syncer => write!(f, "Syncer"),
         }
@@ -54,14 +61,31 @@ impl NodeRole {
         }
     }
 
-    pub fn validator_keypair(&self) -> Option<SecretKey> {
+    /// set the block from which the sealer key of miner becomes a validator
+    pub fn set_activation_block(&mut self, block: u64) {
+        if let NodeRole::Miner {
+            activation_block, ..
+        } = self
+        {
+            *activation_block = Some(block);
+        }
+    }
+
+    pub fn activation_block(&self) -> Option<u64> {
+        match self {
+            NodeRole::Miner {
+                activation_block, ..
+            } => *activation_block,
+            _ => None,
+        }
+    }
+
+    pub fn validator_keypair(&self) -> Option<KeyPair> {
         match self {
             NodeRole::Transactor | NodeRole::Syncer => None,
             NodeRole::Miner {
-                index,
-                sealer_mnemonic,
-                ..
-            } => match super::generate_keypair_with_index(sealer_mnemonic, *index) {
+                index, sealer_key, ..
+            } => match sealer_key.secret_key(*index) {
                 Ok(kp) => Some(kp),
                 _ => None,
             },
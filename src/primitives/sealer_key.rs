@@ -0,0 +1,103 @@
+use ethereum_types::Address;
+use ethsign::{keyfile::KeyFile, Protected};
+use std::path::PathBuf;
+
+use crate::utils::{self, env_var::from_env};
+
+use super::{Error, KeyPair, SealerMnemonic};
+
+/// where a miner takes its sealer key from, so that a miner can be given its own key instead of
+/// the mnemonic of all sealer keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SealerKeySource {
+    /// V3 keystore encrypted by the password in password file
+    Keystore {
+        key_file: PathBuf,
+        password_file: PathBuf,
+    },
+
+    /// file of hex encoded secret key, like a mounted Kubernetes secret
+    RawKeyFile(PathBuf),
+
+    /// key at miner index derived from sealer mnemonic
+    Mnemonic(SealerMnemonic),
+}
+
+impl SealerKeySource {
+    /// key source from SEALER_KEYSTORE_FILE with SEALER_PASSWORD_FILE, SEALER_KEY_FILE or the
+    /// sealer mnemonic, in this order
+    pub fn from_env() -> Result<SealerKeySource, Error> {
+        let source = if let Ok(key_file) = from_env("SEALER_KEYSTORE_FILE") {
+            SealerKeySource::Keystore {
+                key_file: PathBuf::from(key_file),
+                password_file: PathBuf::from(from_env("SEALER_PASSWORD_FILE")?),
+            }
+        } else if let Ok(key_file) = from_env("SEALER_KEY_FILE") {
+            SealerKeySource::RawKeyFile(PathBuf::from(key_file))
+        } else {
+            SealerKeySource::Mnemonic(SealerMnemonic::from_env()?)
+        };
+        Ok(source)
+    }
+
+    /// sealer key of miner at `index`, which is only used for mnemonic
+    pub fn secret_key(&self, index: usize) -> Result<KeyPair, Error> {
+        match self {
+            SealerKeySource::Keystore {
+                key_file,
+                password_file,
+            } => {
+                let invalid = |reason: &str| {
+                    Error::InvalidSealerKey(format!("{}: {}", key_file.display(), reason))
+                };
+                let keystore: KeyFile = serde_json::from_str(&std::fs::read_to_string(key_file)?)?;
+                let password = std::fs::read_to_string(password_file)?;
+                let password = Protected::from(password.trim_end().as_bytes());
+                let secret = keystore
+                    .crypto
+                    .decrypt(&password)
+                    .map_err(|_| invalid("wrong password"))?;
+                let secret_key = KeyPair::from_raw(&secret).map_err(|_| invalid("invalid key"))?;
+                match keystore.address {
+                    Some(ref address) if address.0[..] != secret_key.public().address()[..] => {
+                        Err(invalid("address does not match key"))
+                    }
+                    _ => Ok(secret_key),
+                }
+            }
+            SealerKeySource::RawKeyFile(key_file) => {
+                let content = std::fs::read_to_string(key_file)?;
+                utils::hex_to_bytes(content.trim())
+                    .and_then(|secret| KeyPair::from_raw(&secret).ok())
+                    .ok_or_else(|| {
+                        Error::InvalidSealerKey(format!("{}: invalid key", key_file.display()))
+                    })
+            }
+            SealerKeySource::Mnemonic(sealer_mnemonic) => sealer_mnemonic.secret_key(index),
+        }
+    }
+}
+
+/// validator addresses from VALIDATOR_ADDRESSES, a comma separated list, so that the bootnode
+/// does not need the sealer mnemonic, None if it is absent
+pub fn validator_addresses_from_env() -> Result<Option<Vec<Address>>, Error> {
+    let addresses = match from_env("VALIDATOR_ADDRESSES") {
+        Ok(addresses) => addresses,
+        Err(_) => return Ok(None),
+    };
+
+    addresses
+        .split(',')
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+        .map(|address| {
+            utils::clean_0x(address)
+                .parse()
+                .map_err(|_| Error::InvalidNetworkSetting {
+                    name: "VALIDATOR_ADDRESSES".to_owned(),
+                    value: address.to_owned(),
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
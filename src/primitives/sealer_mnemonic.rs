@@ -0,0 +1,60 @@
+use hdwallet::mnemonic::{Language, Mnemonic};
+
+use crate::utils::env_var::from_env;
+
+use super::{generate_keypair_with_hdpath, Error, KeyPair, DEFAULT_HD_PATH};
+
+/// mnemonic of sealer keys with its HD path template and BIP-39 passphrase, miners and bootnode
+/// must be given the same settings so that validator addresses match
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SealerMnemonic {
+    mnemonic: Mnemonic,
+    passphrase: String,
+    pub hd_path: String,
+}
+
+impl std::fmt::Debug for SealerMnemonic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SealerMnemonic(hdpath: {})", self.hd_path)
+    }
+}
+
+impl SealerMnemonic {
+    pub fn new(
+        phrase: &str,
+        hd_path: Option<String>,
+        passphrase: Option<String>,
+    ) -> Result<SealerMnemonic, Error> {
+        let mnemonic = Mnemonic::try_from(Language::English, phrase)
+            .map_err(|_| Error::InvalidMnemonicPhrase(phrase.to_owned()))?;
+        let sealer_mnemonic = SealerMnemonic {
+            mnemonic,
+            passphrase: passphrase.unwrap_or_default(),
+            hd_path: hd_path.unwrap_or_else(|| DEFAULT_HD_PATH.to_owned()),
+        };
+        // fail early on invalid HD path
+        sealer_mnemonic.secret_key(0)?;
+
+        Ok(sealer_mnemonic)
+    }
+
+    /// sealer mnemonic from SEALER_MNEMONIC_PHRASE and SEALER_HD_PATH, the passphrase is taken
+    /// from SEALER_MNEMONIC_PASSPHRASE or the file at SEALER_MNEMONIC_PASSPHRASE_FILE
+    pub fn from_env() -> Result<SealerMnemonic, Error> {
+        let phrase = from_env("SEALER_MNEMONIC_PHRASE")?;
+        let hd_path = from_env("SEALER_HD_PATH").ok();
+        let passphrase = match from_env("SEALER_MNEMONIC_PASSPHRASE_FILE") {
+            Ok(file_path) => Some(std::fs::read_to_string(file_path)?.trim_end().to_owned()),
+            Err(_) => from_env("SEALER_MNEMONIC_PASSPHRASE").ok(),
+        };
+        SealerMnemonic::new(&phrase, hd_path, passphrase)
+    }
+
+    pub fn secret_key(&self, index: usize) -> Result<KeyPair, Error> {
+        generate_keypair_with_hdpath(&self.mnemonic, &self.passphrase, &self.hd_path, index)
+    }
+
+    pub fn secret_keys(&self, count: usize) -> Result<Vec<KeyPair>, Error> {
+        (0..count).map(|index| self.secret_key(index)).collect()
+    }
+}
@@ -0,0 +1,81 @@
+use ethereum_types::{Address, U256};
+use hdwallet::mnemonic::{Language, Mnemonic};
+
+use super::{generate_keypair_with_hdpath, Error, KeyPair, NetworkConfig, DEFAULT_HD_PATH};
+
+lazy_static! {
+    /// 1000 ether
+    static ref DEFAULT_TEST_ACCOUNT_BALANCE: U256 = U256::from(1000) * U256::from(10).pow(18.into());
+}
+
+/// funded accounts for tests, derived from a mnemonic other than the sealer mnemonic, so that
+/// test suites can sign transactions without sealer keys
+#[derive(Clone)]
+pub struct TestAccounts {
+    mnemonic: Mnemonic,
+    pub count: usize,
+    pub balance: U256,
+    pub hdpath: String,
+}
+
+impl std::fmt::Debug for TestAccounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "TestAccounts(count: {}, balance: {}, hdpath: {})",
+            self.count, self.balance, self.hdpath
+        )
+    }
+}
+
+impl TestAccounts {
+    /// test accounts of network config, None if no faucet mnemonic is given
+    pub fn from_network_config(config: &NetworkConfig) -> Result<Option<TestAccounts>, Error> {
+        let phrase = match config.faucet_mnemonic {
+            Some(ref phrase) => phrase,
+            None => return Ok(None),
+        };
+        let mnemonic = Mnemonic::try_from(Language::English, phrase)
+            .map_err(|_| Error::InvalidMnemonicPhrase(phrase.clone()))?;
+
+        let count = match config.test_account_count {
+            Some(count) => count,
+            None => return Err(Error::MissingNetworkSetting("testAccountCount".to_owned())),
+        };
+
+        let test_accounts = TestAccounts {
+            mnemonic,
+            count,
+            balance: config
+                .test_account_balance
+                .map(|balance| balance.0)
+                .unwrap_or(*DEFAULT_TEST_ACCOUNT_BALANCE),
+            hdpath: config
+                .test_account_hd_path
+                .clone()
+                .unwrap_or_else(|| DEFAULT_HD_PATH.to_owned()),
+        };
+        // fail early on invalid HD path
+        test_accounts.secret_key(0)?;
+
+        Ok(Some(test_accounts))
+    }
+
+    pub fn secret_key(&self, index: usize) -> Result<KeyPair, Error> {
+        generate_keypair_with_hdpath(&self.mnemonic, "", &self.hdpath, index)
+    }
+
+    pub fn secret_keys(&self) -> Result<Vec<KeyPair>, Error> {
+        (0..self.count)
+            .map(|index| self.secret_key(index))
+            .collect()
+    }
+
+    pub fn addresses(&self) -> Result<Vec<Address>, Error> {
+        Ok(self
+            .secret_keys()?
+            .iter()
+            .map(|secret_key| Address::from(*secret_key.public().address()))
+            .collect())
+    }
+}
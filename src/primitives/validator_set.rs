@@ -0,0 +1,365 @@
+use ethereum_types::{Address, H256, U256};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use crate::utils;
+
+use super::{AccountState, Error};
+
+/// validator set contract and the validators stored in it at genesis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorContract {
+    pub address: Address,
+    pub validators: Vec<Address>,
+
+    /// storage slot of `address[] validators` of contract, the first state variable by default
+    #[serde(default)]
+    pub storage_slot: U256,
+}
+
+/// validator set of Aura and Tendermint, in the format of Parity spec.json
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValidatorSet {
+    /// fixed list of validators
+    List(Vec<Address>),
+
+    /// validators are read from contract, misbehaviour is not reported
+    SafeContract(ValidatorContract),
+
+    /// validators are read from contract, misbehaviour is reported to the contract
+    Contract(ValidatorContract),
+
+    /// validator sets keyed by the block from which they are active
+    Multi(BTreeMap<u64, ValidatorSet>),
+}
+
+impl ValidatorSet {
+    /// validator set from `validators` engine parameter, validator set contracts are deployed
+    /// into `account_states` with their validators in storage
+    ///
+    /// validators of an entry are given as addresses or derived by `derive_validators` from
+    /// `minerCount`, the miner count of network is used if both are absent
+    ///
+    /// validators of a contract are stored as `address[]` at `storageSlot` of the contract,
+    /// which is slot 0 if absent
+    ///
+    /// ```json
+    /// {
+    ///   "multi": {
+    ///     "0": { "list": { "minerCount": 3 } },
+    ///     "1000": { "safeContract": { "address": "0x...", "code": "0x...", "minerCount": 5 } },
+    ///     "5000": { "list": ["0x...", "0x..."] }
+    ///   }
+    /// }
+    /// ```
+    pub fn from_engine_parameter<F>(
+        parameter: &JsonValue,
+        derive_validators: &F,
+        account_states: &mut HashMap<Address, AccountState>,
+    ) -> Result<ValidatorSet, Error>
+    where
+        F: Fn(Option<usize>) -> Result<Vec<Address>, Error>,
+    {
+        let invalid = || Error::InvalidValidatorSet(parameter.to_string());
+
+        let (kind, entry) = match parameter {
+            JsonValue::Null => return Ok(ValidatorSet::List(derive_validators(None)?)),
+            JsonValue::String(kind) if kind == "list" => {
+                return Ok(ValidatorSet::List(derive_validators(None)?));
+            }
+            JsonValue::Object(set) if set.len() == 1 => {
+                set.iter().next().expect("validator set has one entry; qed")
+            }
+            _ => return Err(invalid()),
+        };
+
+        match kind.as_str() {
+            "list" => Ok(ValidatorSet::List(entry_validators(
+                entry,
+                derive_validators,
+            )?)),
+            "safeContract" => Ok(ValidatorSet::SafeContract(deploy_contract(
+                kind,
+                entry,
+                entry_validators(entry, derive_validators)?,
+                account_states,
+            )?)),
+            "contract" => Ok(ValidatorSet::Contract(deploy_contract(
+                kind,
+                entry,
+                entry_validators(entry, derive_validators)?,
+                account_states,
+            )?)),
+            "multi" => {
+                let mut sets = BTreeMap::default();
+                for (block, set) in entry.as_object().ok_or_else(invalid)? {
+                    let block = parse_block(block).ok_or_else(invalid)?;
+                    if set.get("multi").is_some() {
+                        return Err(invalid());
+                    }
+                    sets.insert(
+                        block,
+                        Self::from_engine_parameter(set, derive_validators, account_states)?,
+                    );
+                }
+
+                if !sets.contains_key(&0) {
+                    return Err(Error::InvalidValidatorSet(
+                        "multi validator set must start at block 0".to_owned(),
+                    ));
+                }
+                Ok(ValidatorSet::Multi(sets))
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// validator set from `validators` of Parity spec.json, validators of contracts are read
+    /// from storage slot 0 of contract accounts, which are looked up by address regardless of
+    /// case and 0x prefix of account keys
+    pub fn from_parity_spec(
+        validators: &JsonValue,
+        accounts: &JsonValue,
+    ) -> Result<ValidatorSet, Error> {
+        let invalid = || Error::InvalidParityChainSpec(validators.to_string());
+        let parse_address = |address: &JsonValue| {
+            address
+                .as_str()
+                .and_then(|address| Address::from_str(utils::clean_0x(address)).ok())
+                .ok_or_else(|| Error::InvalidParityChainSpec(address.to_string()))
+        };
+
+        if let Some(list) = validators.get("list") {
+            return list
+                .as_array()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(parse_address)
+                .collect::<Result<Vec<_>, _>>()
+                .map(ValidatorSet::List);
+        }
+
+        let contract = |address: &JsonValue| -> Result<ValidatorContract, Error> {
+            let address = parse_address(address)?;
+            let storage = accounts
+                .as_object()
+                .and_then(|accounts| {
+                    accounts.iter().find(|(key, _)| {
+                        Address::from_str(utils::clean_0x(key)).ok() == Some(address)
+                    })
+                })
+                .map(|(_, account)| &account["storage"])
+                .unwrap_or(&JsonValue::Null);
+            let storage_slot = U256::zero();
+            Ok(ValidatorContract {
+                address,
+                validators: validators_from_storage(storage, storage_slot),
+                storage_slot,
+            })
+        };
+
+        if let Some(address) = validators.get("safeContract") {
+            return Ok(ValidatorSet::SafeContract(contract(address)?));
+        }
+
+        if let Some(address) = validators.get("contract") {
+            return Ok(ValidatorSet::Contract(contract(address)?));
+        }
+
+        if let Some(multi) = validators.get("multi").and_then(JsonValue::as_object) {
+            return multi
+                .iter()
+                .map(|(block, set)| -> Result<(u64, ValidatorSet), Error> {
+                    Ok((
+                        parse_block(block).ok_or_else(invalid)?,
+                        Self::from_parity_spec(set, accounts)?,
+                    ))
+                })
+                .collect::<Result<BTreeMap<_, _>, Error>>()
+                .map(ValidatorSet::Multi);
+        }
+
+        Err(invalid())
+    }
+
+    /// all validators of the set in order of activation, including those of later transitions
+    pub fn validators(&self) -> Vec<Address> {
+        match self {
+            ValidatorSet::List(validators) => validators.clone(),
+            ValidatorSet::SafeContract(contract) | ValidatorSet::Contract(contract) => {
+                contract.validators.clone()
+            }
+            ValidatorSet::Multi(sets) => {
+                sets.values().fold(Vec::new(), |mut all_validators, set| {
+                    for validator in set.validators() {
+                        if !all_validators.contains(&validator) {
+                            all_validators.push(validator);
+                        }
+                    }
+                    all_validators
+                })
+            }
+        }
+    }
+
+    /// validators which are active at `block`, validators of contracts are those at genesis
+    pub fn validators_at(&self, block: u64) -> Vec<Address> {
+        match self {
+            ValidatorSet::Multi(sets) => sets
+                .range(..=block)
+                .next_back()
+                .map(|(_, set)| set.validators_at(block))
+                .unwrap_or_default(),
+            _ => self.validators(),
+        }
+    }
+
+    /// block from which `validator` is active, None if it is never a validator
+    pub fn activation_block(&self, validator: &Address) -> Option<u64> {
+        match self {
+            ValidatorSet::Multi(sets) => sets
+                .iter()
+                .find(|(_, set)| set.validators().contains(validator))
+                .map(|(block, _)| *block),
+            _ if self.validators().contains(validator) => Some(0),
+            _ => None,
+        }
+    }
+
+    /// `validators` of engine parameters in Parity spec.json
+    pub fn as_parity_json(&self) -> JsonValue {
+        match self {
+            ValidatorSet::List(validators) => json!({ "list": validators }),
+            ValidatorSet::SafeContract(contract) => json!({ "safeContract": contract.address }),
+            ValidatorSet::Contract(contract) => json!({ "contract": contract.address }),
+            ValidatorSet::Multi(sets) => {
+                let sets: serde_json::Map<_, _> = sets
+                    .iter()
+                    .map(|(block, set)| (block.to_string(), set.as_parity_json()))
+                    .collect();
+                json!({ "multi": sets })
+            }
+        }
+    }
+}
+
+/// validators of a validator set entry: a list of addresses, an object with `addresses` or
+/// `minerCount`, or nothing for the miner count of network
+fn entry_validators<F>(entry: &JsonValue, derive_validators: &F) -> Result<Vec<Address>, Error>
+where
+    F: Fn(Option<usize>) -> Result<Vec<Address>, Error>,
+{
+    let addresses = match entry {
+        JsonValue::Array(_) => Some(entry),
+        JsonValue::Object(entry) => entry.get("addresses"),
+        _ => None,
+    };
+
+    match addresses {
+        Some(addresses) => addresses
+            .as_array()
+            .ok_or_else(|| Error::InvalidValidatorSet(addresses.to_string()))?
+            .iter()
+            .map(|address| {
+                address
+                    .as_str()
+                    .and_then(|address| Address::from_str(utils::clean_0x(address)).ok())
+                    .ok_or_else(|| Error::InvalidValidatorSet(address.to_string()))
+            })
+            .collect(),
+        None => derive_validators(entry["minerCount"].as_u64().map(|count| count as usize)),
+    }
+}
+
+/// deploy validator set contract into `account_states` with `validators` in its storage
+fn deploy_contract(
+    kind: &str,
+    entry: &JsonValue,
+    validators: Vec<Address>,
+    account_states: &mut HashMap<Address, AccountState>,
+) -> Result<ValidatorContract, Error> {
+    let address = entry["address"]
+        .as_str()
+        .and_then(|address| Address::from_str(utils::clean_0x(address)).ok())
+        .ok_or_else(|| Error::InvalidValidatorSet(entry.to_string()))?;
+    let storage_slot = match entry.get("storageSlot") {
+        Some(slot) => utils::maybe_u256_from_json_value(slot)
+            .ok_or_else(|| Error::InvalidValidatorSet(entry.to_string()))?,
+        None => U256::zero(),
+    };
+
+    let mut account = account_states.remove(&address).unwrap_or_default();
+    if let Some(code) = entry["code"].as_str() {
+        account.code = Some(code.to_owned());
+    }
+    if account.code.is_none() {
+        return Err(Error::MissingNetworkSetting(format!(
+            "validators.{}.code",
+            kind
+        )));
+    }
+    account
+        .storage
+        .get_or_insert_with(BTreeMap::default)
+        .extend(validators_storage(&validators, storage_slot));
+    account_states.insert(address, account);
+
+    Ok(ValidatorContract {
+        address,
+        validators,
+        storage_slot,
+    })
+}
+
+fn parse_block(block: &str) -> Option<u64> {
+    utils::maybe_u256(block).and_then(|block| {
+        if block > U256::from(u64::max_value()) {
+            None
+        } else {
+            Some(block.low_u64())
+        }
+    })
+}
+
+/// storage of `address[]` at `slot`: length in the slot, items from the keccak256 hash of the
+/// slot
+fn validators_storage(validators: &[Address], slot: U256) -> BTreeMap<U256, U256> {
+    let first_item = array_item_slot(slot);
+
+    let mut storage = BTreeMap::default();
+    storage.insert(slot, U256::from(validators.len()));
+    for (index, validator) in validators.iter().enumerate() {
+        storage.insert(
+            first_item + U256::from(index),
+            U256::from(&H256::from(*validator)[..]),
+        );
+    }
+    storage
+}
+
+fn validators_from_storage(storage: &JsonValue, slot: U256) -> Vec<Address> {
+    let slot_value = |slot: U256| {
+        storage
+            .as_object()
+            .and_then(|storage| {
+                storage
+                    .iter()
+                    .find(|(key, _)| utils::maybe_u256(key) == Some(slot))
+            })
+            .and_then(|(_, value)| utils::maybe_u256_from_json_value(value))
+            .unwrap_or_default()
+    };
+
+    let first_item = array_item_slot(slot);
+    let count = slot_value(slot).low_u64();
+
+    (0..count)
+        .map(|index| Address::from(H256::from(slot_value(first_item + U256::from(index)))))
+        .collect()
+}
+
+fn array_item_slot(slot: U256) -> U256 {
+    U256::from(&utils::keccak256(&H256::from(slot)[..])[..])
+}
@@ -0,0 +1,183 @@
+//! Minimal Solidity ABI encoder for constructor arguments and storage values
+
+use ethereum_types::{Address, H256, U256};
+use serde_json::Value as JsonValue;
+use std::str::FromStr;
+
+use super::{clean_0x, hex_to_bytes, maybe_u256, maybe_u256_from_json_value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamType {
+    Uint(usize),
+    Int(usize),
+    Address,
+    Bool,
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<ParamType>),
+    FixedArray(Box<ParamType>, usize),
+}
+
+impl ParamType {
+    /// parameter type from its canonical name, tuples are not supported
+    pub fn parse(name: &str) -> Option<ParamType> {
+        let name = name.trim();
+        if name.ends_with(']') {
+            let open = name.rfind('[')?;
+            let item = Box::new(ParamType::parse(&name[..open])?);
+            let length = &name[open + 1..name.len() - 1];
+            return if length.is_empty() {
+                Some(ParamType::Array(item))
+            } else {
+                Some(ParamType::FixedArray(item, length.parse().ok()?))
+            };
+        }
+
+        let bits = |prefix: &str| -> Option<usize> {
+            let bits = &name[prefix.len()..];
+            let bits = if bits.is_empty() {
+                256
+            } else {
+                bits.parse().ok()?
+            };
+            if bits > 0 && bits <= 256 && bits % 8 == 0 {
+                Some(bits)
+            } else {
+                None
+            }
+        };
+
+        match name {
+            "address" => Some(ParamType::Address),
+            "bool" => Some(ParamType::Bool),
+            "bytes" => Some(ParamType::Bytes),
+            "string" => Some(ParamType::String),
+            _ if name.starts_with("uint") => bits("uint").map(ParamType::Uint),
+            _ if name.starts_with("int") => bits("int").map(ParamType::Int),
+            _ if name.starts_with("bytes") => match name["bytes".len()..].parse() {
+                Ok(size) if size > 0 && size <= 32 => Some(ParamType::FixedBytes(size)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn is_dynamic(&self) -> bool {
+        match self {
+            ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+            ParamType::FixedArray(item, _) => item.is_dynamic(),
+            _ => false,
+        }
+    }
+
+    /// size of head in words
+    fn head_words(&self) -> usize {
+        match self {
+            ParamType::FixedArray(item, length) if !self.is_dynamic() => item.head_words() * length,
+            _ => 1,
+        }
+    }
+}
+
+/// ABI encoding of `values` as a tuple of `types`, which is appended to the creation code of
+/// contract as constructor arguments
+pub fn encode(types: &[ParamType], values: &[JsonValue]) -> Option<Vec<u8>> {
+    if types.len() != values.len() {
+        return None;
+    }
+
+    let mut heads = Vec::new();
+    let mut tails = Vec::new();
+    let heads_len: usize = types.iter().map(|kind| kind.head_words() * 32).sum();
+
+    for (kind, value) in types.iter().zip(values) {
+        let encoded = encode_param(kind, value)?;
+        if kind.is_dynamic() {
+            heads.extend_from_slice(&H256::from(U256::from(heads_len + tails.len()))[..]);
+            tails.extend(encoded);
+        } else {
+            heads.extend(encoded);
+        }
+    }
+
+    heads.extend(tails);
+    Some(heads)
+}
+
+fn encode_param(kind: &ParamType, value: &JsonValue) -> Option<Vec<u8>> {
+    match kind {
+        ParamType::Bytes | ParamType::String => {
+            let data = match kind {
+                ParamType::Bytes => hex_to_bytes(value.as_str()?)?,
+                _ => value.as_str()?.as_bytes().to_vec(),
+            };
+            let mut encoded = H256::from(U256::from(data.len())).to_vec();
+            encoded.extend(&data);
+            encoded.resize(32 + (data.len() + 31) / 32 * 32, 0);
+            Some(encoded)
+        }
+        ParamType::Array(item) => {
+            let items = value.as_array()?;
+            let mut encoded = H256::from(U256::from(items.len())).to_vec();
+            encoded.extend(encode(&vec![*item.clone(); items.len()], items)?);
+            Some(encoded)
+        }
+        ParamType::FixedArray(item, length) => {
+            encode(&vec![*item.clone(); *length], value.as_array()?)
+        }
+        _ => encode_word(kind, value).map(|word| word.to_vec()),
+    }
+}
+
+/// ABI encoding of a value type in a single word, fixed bytes are left aligned and signed
+/// integers are in two's complement
+pub fn encode_word(kind: &ParamType, value: &JsonValue) -> Option<H256> {
+    match kind {
+        ParamType::Uint(bits) => {
+            let value = match value {
+                JsonValue::Number(_) | JsonValue::String(_) => maybe_u256_from_json_value(value)?,
+                _ => return None,
+            };
+            if *bits < 256 && value.bits() > *bits {
+                return None;
+            }
+            Some(H256::from(value))
+        }
+        ParamType::Int(bits) => {
+            let (negative, magnitude) = match value {
+                JsonValue::Number(n) => {
+                    let n = n.as_i64()?;
+                    (n < 0, U256::from(n.unsigned_abs()))
+                }
+                JsonValue::String(s) if s.starts_with('-') => (true, maybe_u256(&s[1..])?),
+                JsonValue::String(s) => (false, maybe_u256(s)?),
+                _ => return None,
+            };
+            let limit = U256::one() << (bits - 1);
+            if (negative && magnitude > limit) || (!negative && magnitude >= limit) {
+                return None;
+            }
+            if negative && !magnitude.is_zero() {
+                Some(H256::from(U256::max_value() - (magnitude - 1)))
+            } else {
+                Some(H256::from(magnitude))
+            }
+        }
+        ParamType::Address => {
+            let address = Address::from_str(clean_0x(value.as_str()?)).ok()?;
+            Some(H256::from(address))
+        }
+        ParamType::Bool => Some(H256::from(U256::from(value.as_bool()? as u8))),
+        ParamType::FixedBytes(size) => {
+            let bytes = hex_to_bytes(value.as_str()?)?;
+            if bytes.len() > *size {
+                return None;
+            }
+            let mut word = [0u8; 32];
+            word[..bytes.len()].copy_from_slice(&bytes);
+            Some(H256::from(word))
+        }
+        _ => None,
+    }
+}
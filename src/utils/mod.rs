@@ -1,9 +1,13 @@
-use ethereum_types::U256;
+use ethereum_types::{H256, U256};
 use std::str::FromStr;
 
+pub mod abi;
 pub mod env_var;
 pub mod exit_code;
 mod retry_future;
+pub mod rlp;
+pub mod secret_file;
+pub mod trie;
 
 pub use self::retry_future::RetryFuture;
 
@@ -19,6 +23,63 @@ pub fn to_0xhex<V: std::fmt::LowerHex>(value: &V) -> String {
     format!("0x{:x}", value)
 }
 
+pub fn bytes_to_0xhex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::from("0x"), |mut hex, byte| {
+        hex.push_str(&format!("{:02x}", byte));
+        hex
+    })
+}
+
+/// UUID in hyphenated form from 16 bytes, version and variant bits are set for version 4
+pub fn uuid_from_bytes(bytes: &[u8; 16]) -> String {
+    let mut bytes = *bytes;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = &bytes_to_0xhex(&bytes)[2..];
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// bytes of hex string with or without 0x prefix
+pub fn hex_to_bytes(hex: &str) -> Option<Vec<u8>> {
+    let hex = clean_0x(hex);
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// CRC32 (IEEE) checksum of bytes which follow those of `crc`
+pub fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+pub fn keccak256(bytes: &[u8]) -> H256 {
+    H256::from(tiny_keccak::keccak256(bytes))
+}
+
 pub fn maybe_u256(value: &str) -> Option<U256> {
     if value.starts_with("0x") {
         U256::from_str(&value[2..]).ok()
@@ -34,3 +95,30 @@ pub fn maybe_u256_from_json_value(value: &serde_json::Value) -> Option<U256> {
         _ => None,
     }
 }
+
+/// u64 from number, decimal string or 0x-prefixed hex string, like integers of Parity spec.json
+pub fn maybe_u64_from_json_value(value: &serde_json::Value) -> Option<u64> {
+    maybe_u256_from_json_value(value).and_then(|v| {
+        if v > U256::from(u64::max_value()) {
+            None
+        } else {
+            Some(v.low_u64())
+        }
+    })
+}
+
+/// deserialize optional U256 from number, decimal string or 0x-prefixed hex string
+pub fn deserialize_maybe_u256<'de, D>(deserializer: D) -> Result<Option<U256>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::{de::Error, Deserialize};
+
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => match maybe_u256_from_json_value(&value) {
+            Some(v) => Ok(Some(v)),
+            None => Err(D::Error::custom(format!("invalid quantity: {}", value))),
+        },
+    }
+}
@@ -0,0 +1,113 @@
+//! Minimal RLP encoder for genesis data
+
+use ethereum_types::U256;
+
+pub fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+
+    let mut out = encode_length(bytes.len(), 0x80);
+    out.extend_from_slice(bytes);
+    out
+}
+
+/// integer as big endian bytes without leading zeros
+pub fn encode_u256(value: &U256) -> Vec<u8> {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    let first = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    encode_bytes(&bytes[first..])
+}
+
+pub fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut out = encode_length(payload.len(), 0xc0);
+    out.extend(payload);
+    out
+}
+
+fn encode_length(len: usize, offset: u8) -> Vec<u8> {
+    if len < 56 {
+        return vec![offset + len as u8];
+    }
+
+    let len_bytes = (len as u64).to_be_bytes();
+    let first = len_bytes
+        .iter()
+        .position(|b| *b != 0)
+        .unwrap_or(len_bytes.len());
+
+    let mut out = vec![offset + 55 + (len_bytes.len() - first) as u8];
+    out.extend_from_slice(&len_bytes[first..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use ethereum_types::H256;
+
+    use super::*;
+    use crate::utils::{hex_to_bytes, keccak256, to_0xhex, trie};
+
+    fn hex(hex: &str) -> Vec<u8> {
+        hex_to_bytes(hex).expect("valid hex")
+    }
+
+    #[test]
+    fn encodes_strings_integers_and_lists() {
+        assert_eq!(encode_bytes(b""), hex("0x80"));
+        assert_eq!(encode_bytes(b"\x0f"), hex("0x0f"));
+        assert_eq!(encode_bytes(b"dog"), hex("0x83646f67"));
+        assert_eq!(
+            encode_bytes(b"Lorem ipsum dolor sit amet, consectetur adipisicing elit"),
+            [
+                hex("0xb838"),
+                b"Lorem ipsum dolor sit amet, consectetur adipisicing elit".to_vec()
+            ]
+            .concat()
+        );
+
+        assert_eq!(encode_u256(&U256::zero()), hex("0x80"));
+        assert_eq!(encode_u256(&U256::from(15)), hex("0x0f"));
+        assert_eq!(encode_u256(&U256::from(1024)), hex("0x820400"));
+
+        assert_eq!(encode_list(&[]), hex("0xc0"));
+        assert_eq!(
+            encode_list(&[encode_bytes(b"cat"), encode_bytes(b"dog")]),
+            hex("0xc88363617483646f67")
+        );
+    }
+
+    /// header of Ethereum mainnet genesis block hashes to the published block hash
+    #[test]
+    fn hashes_mainnet_genesis_header() {
+        let empty_trie_root = trie::empty_trie_root();
+        let header = vec![
+            encode_bytes(&H256::zero()[..]),
+            encode_bytes(&keccak256(&encode_list(&[]))[..]),
+            encode_bytes(&[0u8; 20]),
+            encode_bytes(&hex(
+                "0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+            )),
+            encode_bytes(&empty_trie_root[..]),
+            encode_bytes(&empty_trie_root[..]),
+            encode_bytes(&[0u8; 256]),
+            encode_u256(&U256::from(0x4_0000_0000u64)),
+            encode_u256(&U256::zero()),
+            encode_u256(&U256::from(5000)),
+            encode_u256(&U256::zero()),
+            encode_u256(&U256::zero()),
+            encode_bytes(&hex(
+                "0x11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+            )),
+            encode_bytes(&H256::zero()[..]),
+            encode_bytes(&hex("0x0000000000000042")),
+        ];
+
+        assert_eq!(
+            to_0xhex(&keccak256(&encode_list(&header))),
+            "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! Root hash of Merkle Patricia trie for genesis state
+
+use ethereum_types::H256;
+
+use super::{keccak256, rlp};
+
+/// root hash of trie with `entries`, keys are hashed by the caller
+pub fn trie_root(entries: Vec<(Vec<u8>, Vec<u8>)>) -> H256 {
+    let mut entries: Vec<(Vec<u8>, Vec<u8>)> = entries
+        .into_iter()
+        .map(|(key, value)| (to_nibbles(&key), value))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries.dedup_by(|a, b| a.0 == b.0);
+
+    keccak256(&encode_node(&entries, 0))
+}
+
+/// root hash of empty trie
+pub fn empty_trie_root() -> H256 {
+    trie_root(vec![])
+}
+
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|b| vec![b >> 4, b & 0x0f]).collect()
+}
+
+/// compact encoding of nibbles with leaf flag
+fn hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 2 } else { 0 };
+    let mut bytes = Vec::with_capacity(nibbles.len() / 2 + 1);
+
+    let rest = if nibbles.len() % 2 == 1 {
+        bytes.push(((flag + 1) << 4) | nibbles[0]);
+        &nibbles[1..]
+    } else {
+        bytes.push(flag << 4);
+        nibbles
+    };
+
+    for pair in rest.chunks(2) {
+        bytes.push((pair[0] << 4) | pair[1]);
+    }
+    bytes
+}
+
+/// RLP of node which holds sorted `entries` whose first `depth` nibbles are consumed
+fn encode_node(entries: &[(Vec<u8>, Vec<u8>)], depth: usize) -> Vec<u8> {
+    match entries.len() {
+        0 => rlp::encode_bytes(&[]),
+        1 => {
+            let (key, value) = &entries[0];
+            rlp::encode_list(&[
+                rlp::encode_bytes(&hex_prefix(&key[depth..], true)),
+                rlp::encode_bytes(value),
+            ])
+        }
+        _ => {
+            let first = &entries[0].0;
+            let shared = (depth..first.len())
+                .take_while(|i| entries.iter().all(|(key, _)| key.get(*i) == first.get(*i)))
+                .count();
+
+            if shared > 0 {
+                return rlp::encode_list(&[
+                    rlp::encode_bytes(&hex_prefix(&first[depth..depth + shared], false)),
+                    node_reference(encode_node(entries, depth + shared)),
+                ]);
+            }
+
+            let mut items = Vec::with_capacity(17);
+            let mut value = rlp::encode_bytes(&[]);
+            let mut begin = 0;
+            if entries[0].0.len() == depth {
+                value = rlp::encode_bytes(&entries[0].1);
+                begin = 1;
+            }
+
+            for nibble in 0..16u8 {
+                let end = begin
+                    + entries[begin..]
+                        .iter()
+                        .take_while(|(key, _)| key[depth] == nibble)
+                        .count();
+                items.push(node_reference(encode_node(&entries[begin..end], depth + 1)));
+                begin = end;
+            }
+            items.push(value);
+
+            rlp::encode_list(&items)
+        }
+    }
+}
+
+/// nodes shorter than 32 bytes are embedded, others are referenced by hash
+fn node_reference(node: Vec<u8>) -> Vec<u8> {
+    if node.len() < 32 {
+        node
+    } else {
+        rlp::encode_bytes(&keccak256(&node)[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::to_0xhex;
+
+    fn root(entries: &[(&str, &str)]) -> String {
+        to_0xhex(&trie_root(
+            entries
+                .iter()
+                .map(|(key, value)| (key.as_bytes().to_vec(), value.as_bytes().to_vec()))
+                .collect(),
+        ))
+    }
+
+    // vectors of trieanyorder.json in ethereum/tests
+
+    #[test]
+    fn empty_trie_root_is_hash_of_empty_string() {
+        assert_eq!(
+            to_0xhex(&empty_trie_root()),
+            "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+        );
+    }
+
+    #[test]
+    fn root_of_single_item() {
+        assert_eq!(
+            root(&[("A", "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")]),
+            "0xd23786fb4a010da3ce639d66d5e904a11dbc02746d1ce25029e53290cabf28ab"
+        );
+    }
+
+    #[test]
+    fn root_of_dogs() {
+        assert_eq!(
+            root(&[
+                ("doe", "reindeer"),
+                ("dog", "puppy"),
+                ("dogglesworth", "cat"),
+            ]),
+            "0x8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3"
+        );
+    }
+
+    #[test]
+    fn root_of_puppy_does_not_depend_on_order() {
+        assert_eq!(
+            root(&[
+                ("do", "verb"),
+                ("horse", "stallion"),
+                ("doge", "coin"),
+                ("dog", "puppy"),
+            ]),
+            "0x5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84"
+        );
+    }
+}